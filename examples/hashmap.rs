@@ -1,7 +1,7 @@
 extern crate rayon_adaptive;
 use rayon_adaptive::prelude::*;
 use rayon_adaptive::{par_elements, par_keys};
-use std::collections::{HashMap, HashSet};
+use hashbrown::{HashMap, HashSet};
 
 fn main() {
     let h: HashMap<u32, u32> = (0..1000).map(|i| (i, i + 1)).collect();
@@ -0,0 +1,269 @@
+//! Derive macros for `Divisible`, `DivisibleIntoBlocks` and `DivisibleAtIndex`.
+//!
+//! Structures are divided field by field.
+//! By default each field is itself divided but this can be changed with the `divide_by`
+//! attribute:
+//! * `#[divide_by(clone)]` : both halves get a clone of the field
+//! * `#[divide_by(default)]` : left half keeps the field, right half gets `Default::default()`
+//! * `#[divide_by(split_fn = "path")]` : call `path(field)` which returns both halves
+//!
+//! The `Power` associated type is given with the `#[power(...)]` attribute on the structure.
+//! If missing we take the power of the first divided field.
+//!
+//! Since `DivisibleAtIndex` is automatically implemented for all `DivisibleIntoBlocks`
+//! with `IndexedPower`, deriving it only checks at compile time that this is indeed the case.
+//!
+//! Generated code refers to `rayon_adaptive::...` so this crate is meant to be used
+//! through the re-exports of `rayon_adaptive`.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Index, Lit, Meta, NestedMeta,
+    Path, Type,
+};
+
+/// How to obtain the two halves of a field.
+enum Division {
+    Divide,
+    Clone,
+    Default,
+    SplitFn(Path),
+}
+
+struct Field {
+    member: TokenStream2,
+    ty: Type,
+    division: Division,
+}
+
+impl Field {
+    fn is_divided(&self) -> bool {
+        matches!(self.division, Division::Divide)
+    }
+}
+
+fn parse_division(attrs: &[Attribute]) -> syn::Result<Division> {
+    let mut division = Division::Divide;
+    for attr in attrs.iter().filter(|a| a.path.is_ident("divide_by")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new_spanned(meta, "expected #[divide_by(...)]")),
+        };
+        for nested in list.nested.iter() {
+            division = match nested {
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("clone") => Division::Clone,
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("default") => Division::Default,
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("split_fn") => {
+                    match &nv.lit {
+                        Lit::Str(s) => Division::SplitFn(s.parse()?),
+                        lit => return Err(Error::new_spanned(lit, "expected a function path")),
+                    }
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        nested,
+                        "expected one of `clone`, `default` or `split_fn = \"path\"`",
+                    ))
+                }
+            };
+        }
+    }
+    Ok(division)
+}
+
+fn parse_fields(input: &DeriveInput) -> syn::Result<(Vec<Field>, bool)> {
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "divisible derives are only available on structures",
+            ))
+        }
+    };
+    let named = matches!(data.fields, Fields::Named(_));
+    let fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => quote!(#ident),
+                None => {
+                    let index = Index::from(index);
+                    quote!(#index)
+                }
+            };
+            Ok(Field {
+                member,
+                ty: field.ty.clone(),
+                division: parse_division(&field.attrs)?,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    if !fields.iter().any(Field::is_divided) {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "at least one field needs to be divided",
+        ));
+    }
+    Ok((fields, named))
+}
+
+fn parse_power(input: &DeriveInput, fields: &[Field]) -> syn::Result<TokenStream2> {
+    match input.attrs.iter().find(|a| a.path.is_ident("power")) {
+        Some(attr) => {
+            let power: Type = attr.parse_args()?;
+            Ok(quote!(#power))
+        }
+        None => {
+            let first = fields.iter().find(|f| f.is_divided()).unwrap();
+            let ty = &first.ty;
+            Ok(quote!(<#ty as rayon_adaptive::Divisible>::Power))
+        }
+    }
+}
+
+/// Generate the code building both halves.
+/// `divide` is the function called on divided fields.
+fn halves(fields: &[Field], named: bool, divide: TokenStream2) -> TokenStream2 {
+    let lefts: Vec<_> = (0..fields.len())
+        .map(|i| syn::Ident::new(&format!("left_{}", i), Span::call_site()))
+        .collect();
+    let rights: Vec<_> = (0..fields.len())
+        .map(|i| syn::Ident::new(&format!("right_{}", i), Span::call_site()))
+        .collect();
+    let splits =
+        fields
+            .iter()
+            .zip(lefts.iter().zip(rights.iter()))
+            .map(|(field, (left, right))| {
+                let member = &field.member;
+                match &field.division {
+                    Division::Divide => quote! {
+                        let (#left, #right) = (#divide)(self.#member);
+                    },
+                    Division::Clone => quote! {
+                        let #left = self.#member.clone();
+                        let #right = self.#member;
+                    },
+                    Division::Default => quote! {
+                        let #left = self.#member;
+                        let #right = Default::default();
+                    },
+                    Division::SplitFn(path) => quote! {
+                        let (#left, #right) = #path(self.#member);
+                    },
+                }
+            });
+    let members: Vec<_> = fields.iter().map(|f| &f.member).collect();
+    let (left, right) = if named {
+        (
+            quote!(Self { #(#members: #lefts),* }),
+            quote!(Self { #(#members: #rights),* }),
+        )
+    } else {
+        (quote!(Self(#(#lefts),*)), quote!(Self(#(#rights),*)))
+    };
+    quote! {
+        #(#splits)*
+        (#left, #right)
+    }
+}
+
+fn derive_divisible(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let (fields, named) = parse_fields(&input)?;
+    let power = parse_power(&input, &fields)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let length = fields
+        .iter()
+        .filter(|f| f.is_divided())
+        .map(|f| {
+            let member = &f.member;
+            quote!(rayon_adaptive::Divisible::base_length(&self.#member))
+        })
+        .fold(None, |min_length, length| match min_length {
+            None => Some(length),
+            Some(min_length) => Some(quote!(std::cmp::min(#min_length, #length))),
+        })
+        .unwrap();
+    let body = halves(&fields, named, quote!(rayon_adaptive::Divisible::divide));
+    let ratio_body = halves(
+        &fields,
+        named,
+        quote!(|d| rayon_adaptive::Divisible::divide_ratio(d, numerator, denominator)),
+    );
+    Ok(quote! {
+        impl #impl_generics rayon_adaptive::Divisible for #name #ty_generics #where_clause {
+            type Power = #power;
+            fn base_length(&self) -> usize {
+                #length
+            }
+            fn divide(self) -> (Self, Self) {
+                #body
+            }
+            fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+                #ratio_body
+            }
+        }
+    })
+}
+
+fn derive_divisible_into_blocks(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let (fields, named) = parse_fields(&input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let body = halves(
+        &fields,
+        named,
+        quote!(|d| rayon_adaptive::DivisibleIntoBlocks::divide_at(d, index)),
+    );
+    Ok(quote! {
+        impl #impl_generics rayon_adaptive::DivisibleIntoBlocks for #name #ty_generics #where_clause {
+            fn divide_at(self, index: usize) -> (Self, Self) {
+                #body
+            }
+        }
+    })
+}
+
+fn derive_divisible_at_index(input: DeriveInput) -> syn::Result<TokenStream2> {
+    parse_fields(&input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[allow(dead_code)]
+            fn divisible_at_index_check() {
+                fn check<D: rayon_adaptive::DivisibleAtIndex>() {}
+                check::<Self>()
+            }
+        }
+    })
+}
+
+fn expand(input: TokenStream, derive: fn(DeriveInput) -> syn::Result<TokenStream2>) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Divisible, attributes(power, divide_by))]
+pub fn divisible(input: TokenStream) -> TokenStream {
+    expand(input, derive_divisible)
+}
+
+#[proc_macro_derive(DivisibleIntoBlocks, attributes(power, divide_by))]
+pub fn divisible_into_blocks(input: TokenStream) -> TokenStream {
+    expand(input, derive_divisible_into_blocks)
+}
+
+#[proc_macro_derive(DivisibleAtIndex, attributes(power, divide_by))]
+pub fn divisible_at_index(input: TokenStream) -> TokenStream {
+    expand(input, derive_divisible_at_index)
+}
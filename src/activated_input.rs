@@ -89,6 +89,10 @@ where
         let (input, folder, policy, sizes) = (self.input, self.folder, self.policy, self.sizes);
         let reduce_ref = &reduce_function;
         let length = input.base_length();
+        if length == 0 {
+            // no chunks at all, just convert the identity
+            return folder.to_output(folder.identity(), input);
+        }
         let mut outputs = input
             .chunks(sizes.chain(once(length)))
             .map(|input| schedule(input, &folder, reduce_ref, policy));
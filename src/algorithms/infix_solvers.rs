@@ -143,3 +143,345 @@ pub fn solver_adaptive(inp: &[Token], policy: Policy) -> u64 {
         .reduce(|left, right| left.fuse(&right))
         .evaluate()
 }
+
+/// A semiring: two associative operations with their identities,
+/// multiplication distributing over addition.
+/// Multiplication needs not be commutative.
+pub struct Semiring<T, A, M> {
+    zero: T,
+    one: T,
+    add: A,
+    mul: M,
+}
+
+impl<T, A, M> Semiring<T, A, M>
+where
+    A: Fn(&T, &T) -> T,
+    M: Fn(&T, &T) -> T,
+{
+    /// Create a new semiring out of identities and operations.
+    pub fn new(zero: T, one: T, add: A, mul: M) -> Self {
+        Semiring {
+            zero,
+            one,
+            add,
+            mul,
+        }
+    }
+}
+
+/// Tokens of infix expressions over any semiring.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InfixToken<T> {
+    Num(T),
+    Add,
+    Mult,
+    Open,
+    Close,
+}
+
+impl<T> InfixToken<T> {
+    /// Parse given char, converting digits (as single digit numbers) with `digit`.
+    /// Whitespace is ignored and any other char panics.
+    pub fn from_char<F: Fn(u32) -> T>(c: char, digit: F) -> Option<Self> {
+        match c {
+            '+' => Some(InfixToken::Add),
+            '*' => Some(InfixToken::Mult),
+            '(' => Some(InfixToken::Open),
+            ')' => Some(InfixToken::Close),
+            c if c.is_whitespace() => None,
+            c => Some(InfixToken::Num(digit(
+                c.to_digit(10).expect("unexpected char in expression"),
+            ))),
+        }
+    }
+}
+
+/// Generic `PartialProducts`: a sum of products without any parenthesis.
+/// The first and last products might be continued by the tokens
+/// before and after.
+#[derive(Debug, Clone)]
+struct Terms<T> {
+    products: SmallVec<[T; 3]>,
+}
+
+impl<T: Clone> Terms<T> {
+    fn new<A, M>(semiring: &Semiring<T, A, M>) -> Self {
+        Terms {
+            products: smallvec![semiring.one.clone()],
+        }
+    }
+    fn update_product<A, M: Fn(&T, &T) -> T>(&mut self, num: &T, semiring: &Semiring<T, A, M>) {
+        let last = self.products.last_mut().unwrap();
+        *last = (semiring.mul)(last, num);
+    }
+    fn append_product<A: Fn(&T, &T) -> T, M>(&mut self, semiring: &Semiring<T, A, M>) {
+        self.products.push(semiring.one.clone());
+        self.reduce_products(semiring);
+    }
+    fn fuse<A: Fn(&T, &T) -> T, M: Fn(&T, &T) -> T>(
+        &mut self,
+        other: Self,
+        semiring: &Semiring<T, A, M>,
+    ) {
+        let mut other_products = other.products.into_iter();
+        self.update_product(&other_products.next().unwrap(), semiring);
+        self.products.extend(other_products);
+        self.reduce_products(semiring);
+    }
+    fn reduce_products<A: Fn(&T, &T) -> T, M>(&mut self, semiring: &Semiring<T, A, M>) {
+        if self.products.len() > 3 {
+            let last = self.products.pop().unwrap();
+            let sum = self.products[2..]
+                .iter()
+                .fold(self.products[1].clone(), |s, p| (semiring.add)(&s, p));
+            self.products.truncate(1);
+            self.products.push(sum);
+            self.products.push(last);
+        }
+    }
+    fn evaluate<A: Fn(&T, &T) -> T, M>(self, semiring: &Semiring<T, A, M>) -> T {
+        self.products
+            .iter()
+            .fold(semiring.zero.clone(), |s, p| (semiring.add)(&s, p))
+    }
+}
+
+/// Evaluation state of a block of tokens: after matching all parentheses
+/// inside the block we are left with `c_0 ) c_1 ) ... c_k ) o_0 ( o_1 ( ... ( o_m`
+/// where all `c_i` and `o_i` are `Terms`.
+/// Fusing two blocks matches the opening parentheses of the left one with the
+/// closing parentheses of the right one, like a stack.
+#[derive(Debug, Clone)]
+struct Nested<T> {
+    // terms before each unmatched closing parenthesis
+    closed: Vec<Terms<T>>,
+    // terms after the last unmatched closing parenthesis and after each
+    // unmatched opening parenthesis, never empty
+    opened: Vec<Terms<T>>,
+}
+
+impl<T: Clone> Nested<T> {
+    fn new<A, M>(semiring: &Semiring<T, A, M>) -> Self {
+        Nested {
+            closed: Vec::new(),
+            opened: vec![Terms::new(semiring)],
+        }
+    }
+    fn push<A: Fn(&T, &T) -> T, M: Fn(&T, &T) -> T>(
+        mut self,
+        token: InfixToken<T>,
+        semiring: &Semiring<T, A, M>,
+    ) -> Self {
+        match token {
+            InfixToken::Num(num) => self
+                .opened
+                .last_mut()
+                .unwrap()
+                .update_product(&num, semiring),
+            InfixToken::Add => self.opened.last_mut().unwrap().append_product(semiring),
+            InfixToken::Mult => {}
+            InfixToken::Open => self.opened.push(Terms::new(semiring)),
+            InfixToken::Close => self.close(semiring),
+        }
+        self
+    }
+    /// Close the last opened parenthesis (if any).
+    fn close<A: Fn(&T, &T) -> T, M: Fn(&T, &T) -> T>(&mut self, semiring: &Semiring<T, A, M>) {
+        let inner = self.opened.pop().unwrap();
+        if let Some(outer) = self.opened.last_mut() {
+            outer.update_product(&inner.evaluate(semiring), semiring);
+        } else {
+            self.closed.push(inner);
+            self.opened.push(Terms::new(semiring));
+        }
+    }
+    fn fuse<A: Fn(&T, &T) -> T, M: Fn(&T, &T) -> T>(
+        mut self,
+        other: Self,
+        semiring: &Semiring<T, A, M>,
+    ) -> Self {
+        for terms in other.closed {
+            self.opened.last_mut().unwrap().fuse(terms, semiring);
+            self.close(semiring);
+        }
+        let mut other_opened = other.opened.into_iter();
+        self.opened
+            .last_mut()
+            .unwrap()
+            .fuse(other_opened.next().unwrap(), semiring);
+        self.opened.extend(other_opened);
+        self
+    }
+    /// Final value (if all parentheses matched).
+    fn evaluate<A: Fn(&T, &T) -> T, M>(mut self, semiring: &Semiring<T, A, M>) -> Option<T> {
+        if self.closed.is_empty() && self.opened.len() == 1 {
+            self.opened.pop().map(|terms| terms.evaluate(semiring))
+        } else {
+            None
+        }
+    }
+}
+
+/// Sequentially evaluate an infix expression over given semiring.
+/// Return `None` if parentheses do not match.
+pub fn solver_semiring_seq<T, A, M, I>(tokens: I, semiring: &Semiring<T, A, M>) -> Option<T>
+where
+    T: Clone,
+    A: Fn(&T, &T) -> T,
+    M: Fn(&T, &T) -> T,
+    I: IntoIterator<Item = InfixToken<T>>,
+{
+    tokens
+        .into_iter()
+        .fold(Nested::new(semiring), |state, token| {
+            state.push(token, semiring)
+        })
+        .evaluate(semiring)
+}
+
+/// Evaluate an infix expression over given semiring.
+/// Return `None` if parentheses do not match.
+///
+/// Each block is evaluated as far as possible and parentheses
+/// spanning several blocks get matched when fusing the blocks.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::prelude::*;
+/// use rayon_adaptive::{solver_semiring, InfixToken, Semiring};
+///
+/// // integers modulo a prime
+/// const P: u64 = 1_000_000_007;
+/// let modular = Semiring::new(0, 1, |a, b| (a + b) % P, |a, b| (a * b) % P);
+/// let expression = "(1 + 2) * 3 + 4 * (5 + 6 * (7 + 8))".repeat(10_000).replace(")(", ")+(");
+/// let tokens = expression
+///     .adapt_chars()
+///     .filter_map(|c| InfixToken::from_char(c, |d| d as u64));
+/// assert_eq!(solver_semiring(tokens, &modular), Some(389 * 10_000));
+///
+/// // the (max, +) semiring, over floats
+/// let tropical = Semiring::new(
+///     std::f64::NEG_INFINITY,
+///     0.0,
+///     |a: &f64, b: &f64| a.max(*b),
+///     |a, b| a + b,
+/// );
+/// let tokens = "3 * (2 + 5) + 7".adapt_chars().filter_map(|c| InfixToken::from_char(c, f64::from));
+/// assert_eq!(solver_semiring(tokens, &tropical), Some(8.0));
+/// let tokens = "(1+2))".adapt_chars().filter_map(|c| InfixToken::from_char(c, f64::from));
+/// assert_eq!(solver_semiring(tokens, &tropical), None);
+/// ```
+pub fn solver_semiring<T, A, M, I, R, S>(runner: R, semiring: &Semiring<T, A, M>) -> Option<T>
+where
+    T: Clone + Send + Sync,
+    A: Fn(&T, &T) -> T + Sync,
+    M: Fn(&T, &T) -> T + Sync,
+    I: AdaptiveIterator<Item = InfixToken<T>>,
+    R: AdaptiveIteratorRunner<I, S>,
+    S: Iterator<Item = usize>,
+{
+    runner
+        .fold(
+            || Nested::new(semiring),
+            |state, token| state.push(token, semiring),
+        )
+        .reduce(|left, right| left.fuse(right, semiring))
+        .evaluate(semiring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    /// Random expression with nested parentheses.
+    fn random_expression(depth: usize, terms: usize) -> String {
+        let mut rng = rand::thread_rng();
+        let mut expression = String::new();
+        for term in 0..terms {
+            if term != 0 {
+                expression.push(if rng.gen() { '+' } else { '*' });
+            }
+            if depth > 0 && rng.gen_range(0, 4) == 0 {
+                expression.push('(');
+                expression.push_str(&random_expression(depth - 1, rng.gen_range(1, 20)));
+                expression.push(')');
+            } else {
+                expression.push(std::char::from_digit(rng.gen_range(0, 10), 10).unwrap());
+            }
+        }
+        expression
+    }
+
+    /// Recursive descent evaluation (modulo p) of what `random_expression` generates.
+    fn recursive_evaluation(tokens: &mut std::iter::Peekable<std::str::Chars>) -> u64 {
+        let mut sum = 0;
+        let mut product = 1;
+        while let Some(c) = tokens.next() {
+            match c {
+                '+' => {
+                    sum = (sum + product) % 1_000_003;
+                    product = 1;
+                }
+                '*' => {}
+                '(' => product = product * recursive_evaluation(tokens) % 1_000_003,
+                ')' => break,
+                d => product = product * u64::from(d.to_digit(10).unwrap()) % 1_000_003,
+            }
+        }
+        (sum + product) % 1_000_003
+    }
+
+    #[test]
+    fn semiring_solvers_match_recursive_evaluation() {
+        let modular = Semiring::new(0, 1, |a, b| (a + b) % 1_000_003, |a, b| (a * b) % 1_000_003);
+        // 2x2 matrices do not commute
+        let matrices = Semiring::new(
+            [0u64; 4],
+            [1, 0, 0, 1],
+            |a: &[u64; 4], b: &[u64; 4]| {
+                [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]].map(|e| e % 1_000_003)
+            },
+            |a: &[u64; 4], b: &[u64; 4]| {
+                [
+                    a[0] * b[0] + a[1] * b[2],
+                    a[0] * b[1] + a[1] * b[3],
+                    a[2] * b[0] + a[3] * b[2],
+                    a[2] * b[1] + a[3] * b[3],
+                ]
+                .map(|e| e % 1_000_003)
+            },
+        );
+        let matrix = |d: u32| [u64::from(d), 1, 0, u64::from(d) + 1];
+        for &(depth, terms) in &[(0, 1), (1, 10), (3, 100), (5, 1000)] {
+            let expression = random_expression(depth, terms);
+            let expected = recursive_evaluation(&mut expression.chars().peekable());
+            for policy in &[
+                Policy::Join(10),
+                Policy::Adaptive(1, 100),
+                Policy::DefaultPolicy,
+            ] {
+                let tokens = expression
+                    .adapt_chars()
+                    .filter_map(|c| InfixToken::from_char(c, u64::from))
+                    .with_policy(*policy);
+                assert_eq!(solver_semiring(tokens, &modular), Some(expected));
+                let tokens = expression
+                    .adapt_chars()
+                    .filter_map(move |c| InfixToken::from_char(c, matrix))
+                    .with_policy(*policy);
+                assert_eq!(
+                    solver_semiring(tokens, &matrices),
+                    solver_semiring_seq(
+                        expression
+                            .chars()
+                            .filter_map(|c| InfixToken::from_char(c, matrix)),
+                        &matrices
+                    )
+                );
+            }
+        }
+    }
+}
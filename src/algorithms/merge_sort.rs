@@ -9,7 +9,8 @@ use std::iter::repeat;
 // main related code
 
 /// find subslice without last value in given sorted slice.
-fn subslice_without_last_value<T: Eq>(slice: &[T]) -> &[T] {
+/// Values are compared through given key.
+fn subslice_without_last_value<'s, T, K: Eq, F: Fn(&T) -> K>(slice: &'s [T], key: &F) -> &'s [T] {
     match slice.split_last() {
         Some((target, slice)) => {
             let searching_range_start = repeat(())
@@ -19,12 +20,12 @@ fn subslice_without_last_value<T: Eq>(slice: &[T]) -> &[T] {
                 }) // iterate on all powers of 2
                 .take_while(|&i| i < slice.len())
                 .map(|i| slice.len() - i) // go farther and farther from end of slice
-                .find(|&i| unsafe { slice.get_unchecked(i) != target })
+                .find(|&i| unsafe { key(slice.get_unchecked(i)) != key(target) })
                 .unwrap_or(0);
 
             let index = slice[searching_range_start..]
                 .binary_search_by(|x| {
-                    if x.eq(target) {
+                    if key(x) == key(target) {
                         std::cmp::Ordering::Greater
                     } else {
                         std::cmp::Ordering::Less
@@ -38,7 +39,8 @@ fn subslice_without_last_value<T: Eq>(slice: &[T]) -> &[T] {
 }
 
 /// find subslice without first value in given sorted slice.
-fn subslice_without_first_value<T: Eq>(slice: &[T]) -> &[T] {
+/// Values are compared through given key.
+fn subslice_without_first_value<'s, T, K: Eq, F: Fn(&T) -> K>(slice: &'s [T], key: &F) -> &'s [T] {
     match slice.first() {
         Some(target) => {
             let searching_range_end = repeat(())
@@ -47,12 +49,12 @@ fn subslice_without_first_value<T: Eq>(slice: &[T]) -> &[T] {
                     Some(*acc)
                 }) // iterate on all powers of 2
                 .take_while(|&i| i < slice.len())
-                .find(|&i| unsafe { slice.get_unchecked(i) != target })
+                .find(|&i| unsafe { key(slice.get_unchecked(i)) != key(target) })
                 .unwrap_or_else(|| slice.len());
 
             let index = slice[..searching_range_end]
                 .binary_search_by(|x| {
-                    if x.eq(target) {
+                    if key(x) == key(target) {
                         std::cmp::Ordering::Less
                     } else {
                         std::cmp::Ordering::Greater
@@ -68,22 +70,27 @@ fn subslice_without_first_value<T: Eq>(slice: &[T]) -> &[T] {
 /// Cut sorted slice `slice` around start point, splitting around
 /// all values equal to value at start point.
 /// cost is O(log(|removed part size|))
-fn split_around<T: Eq>(slice: &[T], start: usize) -> (&[T], &[T], &[T]) {
-    let low_slice = subslice_without_last_value(&slice[0..=start]);
-    let high_slice = subslice_without_first_value(&slice[start..]);
+fn split_around<'s, T, K: Eq, F: Fn(&T) -> K>(
+    slice: &'s [T],
+    start: usize,
+    key: &F,
+) -> (&'s [T], &'s [T], &'s [T]) {
+    let low_slice = subslice_without_last_value(&slice[0..=start], key);
+    let high_slice = subslice_without_first_value(&slice[start..], key);
     let equal_slice = &slice[low_slice.len()..slice.len() - high_slice.len()];
     (low_slice, equal_slice, high_slice)
 }
 
 /// split large array at midpoint and small array where needed for merge.
-fn merge_split<'a, T: Ord>(
+fn merge_split<'a, T, K: Ord, F: Fn(&T) -> K>(
     large: &'a [T],
     small: &'a [T],
+    key: &F,
 ) -> ((&'a [T], &'a [T], &'a [T]), (&'a [T], &'a [T], &'a [T])) {
     let middle = large.len() / 2;
-    let split_large = split_around(large, middle);
-    let split_small = match small.binary_search(&large[middle]) {
-        Ok(i) => split_around(small, i),
+    let split_large = split_around(large, middle, key);
+    let split_small = match small.binary_search_by_key(&key(&large[middle]), key) {
+        Ok(i) => split_around(small, i, key),
         Err(i) => {
             let (small1, small3) = small.split_at(i);
             (small1, &small[0..0], small3)
@@ -92,14 +99,19 @@ fn merge_split<'a, T: Ord>(
     (split_large, split_small)
 }
 
-#[derive(Debug)]
-struct FusionSlice<'a, T: 'a> {
+struct FusionSlice<'a, T: 'a, F: 'a> {
     left: EdibleSlice<'a, T>,
     right: EdibleSlice<'a, T>,
     output: EdibleSliceMut<'a, T>,
+    key: &'a F,
 }
 
-impl<'a, T: 'a + Send + Sync + Ord + Copy> Divisible for FusionSlice<'a, T> {
+impl<'a, T, K, F> Divisible for FusionSlice<'a, T, F>
+where
+    T: 'a + Send + Sync + Copy,
+    K: Ord,
+    F: Fn(&T) -> K + Sync,
+{
     type Power = BasicPower;
     fn base_length(&self) -> usize {
         self.output.base_length()
@@ -109,9 +121,9 @@ impl<'a, T: 'a + Send + Sync + Ord + Copy> Divisible for FusionSlice<'a, T> {
         let right = self.right.remaining_slice();
         let output = self.output.into_remaining_slice();
         let ((l1, l2, l3), (r1, r2, r3)) = if left.len() > right.len() {
-            merge_split(left, right)
+            merge_split(left, right, self.key)
         } else {
-            let (r, l) = merge_split(right, left);
+            let (r, l) = merge_split(right, left, self.key);
             (l, r)
         };
         let (o1, ol) = output.split_at_mut(l1.len() + r1.len());
@@ -125,21 +137,30 @@ impl<'a, T: 'a + Send + Sync + Ord + Copy> Divisible for FusionSlice<'a, T> {
                 left: EdibleSlice::new(l1),
                 right: EdibleSlice::new(r1),
                 output: EdibleSliceMut::new(o1),
+                key: self.key,
             },
             FusionSlice {
                 left: EdibleSlice::new(l3),
                 right: EdibleSlice::new(r3),
                 output: EdibleSliceMut::new(o3),
+                key: self.key,
             },
         )
     }
 }
 
-fn fuse<T: Ord + Send + Sync + Copy>(left: &[T], right: &[T], output: &mut [T], policy: Policy) {
+/// Merge sorted `left` and `right` (comparing keys) into `output`.
+fn fuse<T, K, F>(left: &[T], right: &[T], output: &mut [T], policy: Policy, key: &F)
+where
+    T: Send + Sync + Copy,
+    K: Ord,
+    F: Fn(&T) -> K + Sync,
+{
     let slices = FusionSlice {
         left: EdibleSlice::new(left),
         right: EdibleSlice::new(right),
         output: EdibleSliceMut::new(output),
+        key,
     };
 
     slices
@@ -150,7 +171,7 @@ fn fuse<T: Ord + Send + Sync + Copy>(left: &[T], right: &[T], output: &mut [T],
                     let mut left_i = slices.left.iter();
                     let mut right_i = slices.right.iter();
                     for o in slices.output.iter_mut().take(limit) {
-                        let go_left = left_i.peek() <= right_i.peek();
+                        let go_left = left_i.peek().map(key) <= right_i.peek().map(key);
                         *o = if go_left {
                             *left_i.next().unwrap()
                         } else {
@@ -161,7 +182,7 @@ fn fuse<T: Ord + Send + Sync + Copy>(left: &[T], right: &[T], output: &mut [T],
                     let mut left_i = slices.left.iter();
                     let mut right_i = slices.right.iter();
                     for o in slices.output.iter_mut().take(limit) {
-                        let go_left = left_i.peek() <= right_i.peek();
+                        let go_left = left_i.peek().map(key) <= right_i.peek().map(key);
                         if go_left {
                             if left_i.peek().is_none() {
                                 *o = *right_i.next().unwrap();
@@ -200,15 +221,28 @@ struct SortingSlices<'a, T: 'a> {
     i: usize,
 }
 
-impl<'a, T: 'a + Ord + Sync + Copy + Send> SortingSlices<'a, T> {
+impl<'a, T: 'a + Sync + Copy + Send> SortingSlices<'a, T> {
     /// Call parallel merge on the right slices.
-    fn fuse_with_policy(self, other: Self, policy: Policy) -> Self {
+    fn fuse_with_policy(self, other: Self, policy: Policy) -> Self
+    where
+        T: Ord,
+    {
+        self.fuse_by_key(other, policy, &|x: &T| *x)
+    }
+
+    /// Call parallel merge on the right slices, comparing keys.
+    fn fuse_by_key<K: Ord, F: Fn(&T) -> K + Sync>(
+        self,
+        other: Self,
+        policy: Policy,
+        key: &F,
+    ) -> Self {
         let mut left = self;
         let mut right = other;
         // let's try a nice optimization here for nearly sorted arrays.
         // if slices are already sorted and at same index then we do nothing !
         let destination_index = if left.i == right.i
-            && left.s[left.i].last() <= right.s[right.i].first()
+            && left.s[left.i].last().map(key) <= right.s[right.i].first().map(key)
         {
             left.i
         } else {
@@ -220,14 +254,14 @@ impl<'a, T: 'a + Ord + Sync + Copy + Send> SortingSlices<'a, T> {
                 let (right_input, right_output) = right.mut_couple(right_index, destination_index);
                 let output_slice = fuse_slices(left_output, right_output);
                 // if slices are nearly sorted we will resort to memcpy
-                if left_input.last() <= right_input.first() {
+                if left_input.last().map(key) <= right_input.first().map(key) {
                     output_slice[..left_input.base_length()].copy_from_slice(left_input);
                     output_slice[left_input.base_length()..].copy_from_slice(right_input);
-                } else if right_input.last() < left_input.first() {
+                } else if right_input.last().map(key) < left_input.first().map(key) {
                     output_slice[..right_input.base_length()].copy_from_slice(right_input);
                     output_slice[right_input.base_length()..].copy_from_slice(left_input);
                 } else {
-                    fuse(left_input, right_input, output_slice, policy);
+                    fuse(left_input, right_input, output_slice, policy, key);
                 }
             }
             destination_index
@@ -279,7 +313,7 @@ impl<'a, T: 'a + Ord + Sync + Copy + Send> SortingSlices<'a, T> {
     }
 }
 
-impl<'a, T: 'a + Ord + Copy + Sync + Send> Divisible for SortingSlices<'a, T> {
+impl<'a, T: 'a + Copy + Sync + Send> Divisible for SortingSlices<'a, T> {
     type Power = BlockedPower;
     fn base_length(&self) -> usize {
         self.s[0].base_length()
@@ -290,12 +324,116 @@ impl<'a, T: 'a + Ord + Copy + Sync + Send> Divisible for SortingSlices<'a, T> {
     }
 }
 
-impl<'a, T: 'a + Ord + Copy + Sync + Send> DivisibleIntoBlocks for SortingSlices<'a, T> {
+impl<'a, T: 'a + Copy + Sync + Send> DivisibleIntoBlocks for SortingSlices<'a, T> {
     fn divide_at(self, i: usize) -> (Self, Self) {
         self.split_at(i)
     }
 }
 
+/// An indexed iterator together with the sorting slices receiving its elements.
+struct CollectingSlices<'a, I, T: 'a> {
+    input: I,
+    slices: SortingSlices<'a, T>,
+}
+
+impl<'a, I, T> Divisible for CollectingSlices<'a, I, T>
+where
+    I: AdaptiveIndexedIterator<Item = T>,
+    T: 'a + Copy + Sync + Send,
+{
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.input.base_length()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+}
+
+impl<'a, I, T> DivisibleIntoBlocks for CollectingSlices<'a, I, T>
+where
+    I: AdaptiveIndexedIterator<Item = T>,
+    T: 'a + Copy + Sync + Send,
+{
+    fn divide_at(self, i: usize) -> (Self, Self) {
+        let (left_input, right_input) = self.input.divide_at(i);
+        let (left_slices, right_slices) = self.slices.split_at(i);
+        (
+            CollectingSlices {
+                input: left_input,
+                slices: left_slices,
+            },
+            CollectingSlices {
+                input: right_input,
+                slices: right_slices,
+            },
+        )
+    }
+}
+
+/// Collect an indexed iterator into a vector sorted by given key.
+/// Each block gets sorted as soon as it is collected and sorted blocks are then merged
+/// in parallel like in `adaptive_sort`.
+pub(crate) fn collect_sorted_by_key<I, R, S, T, K, F>(runner: R, key: F) -> Vec<T>
+where
+    I: AdaptiveIndexedIterator<Item = T>,
+    R: AdaptiveRunner<I, S>,
+    S: Iterator<Item = usize>,
+    T: Copy + Send + Sync,
+    K: Ord,
+    F: Fn(&T) -> K + Sync,
+{
+    let (input, policy, sizes) = runner.input_policy_sizes();
+    let length = input.base_length();
+    if length == 0 {
+        return Vec::new();
+    }
+    let mut output = Vec::with_capacity(length);
+    let mut tmp_slice1 = Vec::with_capacity(length);
+    let mut tmp_slice2 = Vec::with_capacity(length);
+    unsafe {
+        output.set_len(length);
+        tmp_slice1.set_len(length);
+        tmp_slice2.set_len(length);
+    }
+    {
+        let slices = SortingSlices {
+            s: vec![
+                output.as_mut_slice(),
+                tmp_slice1.as_mut_slice(),
+                tmp_slice2.as_mut_slice(),
+            ],
+            i: 0,
+        };
+        let key_ref = &key;
+        let mut result_slices = CollectingSlices { input, slices }
+            .with_policy(policy)
+            .by_blocks(sizes)
+            .map_reduce(
+                |collecting| {
+                    let mut slices = collecting.slices;
+                    let i = slices.i;
+                    let destination = &mut *slices.s[i];
+                    destination
+                        .iter_mut()
+                        .zip(collecting.input)
+                        .for_each(|(d, e)| *d = e);
+                    destination.sort_by_key(key_ref);
+                    slices
+                },
+                |s1, s2| s1.fuse_by_key(s2, Default::default(), key_ref),
+            );
+
+        if result_slices.i != 0 {
+            let i = result_slices.i;
+            let (destination, source) = result_slices.mut_couple(0, i);
+            destination.copy_from_slice(source);
+        }
+    }
+    output
+}
+
 /// Sort given slice using an adaptive version of merge sort.
 /// For now we require Copy on T.
 /// Sort is stable.
@@ -379,3 +517,41 @@ pub fn adaptive_sort_with_policies<T: Ord + Copy + Send + Sync + std::fmt::Debug
         destination.copy_from_slice(source);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Policy;
+    use rand::Rng;
+
+    #[test]
+    fn collected_sorts_are_stable() {
+        let mut rng = rand::thread_rng();
+        for &size in &[0, 1, 2, 10, 1000, 100_000] {
+            // few distinct keys: lots of duplicates
+            let keys: Vec<u32> = (0..size).map(|_| rng.gen_range(0, 10)).collect();
+            let pairs: Vec<(u32, usize)> = keys.iter().cloned().zip(0..size).collect();
+            let mut expected = pairs.clone();
+            expected.sort_by_key(|p| p.0);
+            let mut sorted_keys = keys.clone();
+            sorted_keys.sort();
+            for &policy in &[
+                Policy::Sequential,
+                Policy::Join(100),
+                Policy::JoinContext(10),
+                Policy::Adaptive(10, 1000),
+                Policy::DefaultPolicy,
+            ] {
+                let runner = || pairs.into_adapt_iter().cloned().with_policy(policy);
+                assert_eq!(runner().sorted_by_key(|p| p.0), expected);
+                assert_eq!(collect_sorted_by_key(runner(), |p| p.0), expected);
+                let sorted = keys
+                    .into_adapt_iter()
+                    .cloned()
+                    .with_policy(policy)
+                    .collect_sorted();
+                assert_eq!(sorted, sorted_keys);
+            }
+        }
+    }
+}
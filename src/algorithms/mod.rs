@@ -10,3 +10,4 @@ pub(crate) mod merge_sort_raw_cut_floor;
 pub(crate) mod merge_sort_raw_logs;
 pub(crate) mod merge_sort_swap_blocks;
 pub(crate) mod prefix;
+pub(crate) mod segmented;
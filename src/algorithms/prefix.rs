@@ -1,11 +1,22 @@
 //! Adaptive prefix algorithm.
 //! No macro blocks.
-use crate::{prelude::*, EdibleSliceMut};
+use crate::traits::IndexedPower;
+use crate::{fuse_slices, prelude::*, EdibleSliceMut};
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
+use std::mem::MaybeUninit;
+use std::slice;
 
 /// Run adaptive prefix algortihm on given slice.
 /// Each element is replaced by folding with op since beginning of the slice.
+/// New elements come first: each prefix is `op(element, previous_prefix)`.
 /// It requires an associative operation.
 ///
+/// The master thread computes prefixes sequentially from the start while helpers
+/// compute local prefixes on the parts they steal.
+/// Each time the master retrieves a helped part it spawns the parallel update of this part
+/// (with its current prefix) and directly jumps over it,
+/// overlapping the updates with the rest of the computation.
+///
 /// # Example
 ///
 /// ```
@@ -20,35 +31,54 @@ where
     T: Send + Sync + Clone,
     O: Fn(&T, &T) -> T + Sync,
 {
-    let input = EdibleSliceMut::new(v);
-    input
-        .work(|mut slice, limit| {
-            let c = {
-                let mut elements = slice.iter_mut().take(limit);
-                let mut c = elements.next().unwrap().clone();
-                for e in elements {
-                    *e = op(e, &c);
-                    c = e.clone();
-                }
-                c
-            };
-            // pre-update next one
-            if let Some(e) = slice.peek() {
-                *e = op(e, &c);
-            }
-            slice
-        })
-        .map(|slice| slice.slice())
-        .into_iter()
-        .fold(
+    let op = &op;
+    rayon::scope(|s| {
+        v.cutting_fold(
+            || None,
+            |possible_previous_slice: Option<&mut [T]>, slice| {
+                let previous = possible_previous_slice
+                    .as_ref()
+                    .and_then(|previous_slice| previous_slice.last().cloned());
+                local_prefix(slice, previous, op);
+                Some(match possible_previous_slice {
+                    Some(previous_slice) => fuse_slices(previous_slice, slice),
+                    None => slice,
+                })
+            },
+        )
+        .helping_cutting_fold(
             None,
-            |potential_previous_slice: Option<&mut [T]>, current_slice| {
-                if let Some(previous_slice) = potential_previous_slice {
-                    update(current_slice, previous_slice.last().cloned().unwrap(), &op);
+            |previous, slice| local_prefix(slice, previous, op),
+            |previous, dirty_slice| match (previous, dirty_slice) {
+                (Some(increment), Some(retrieved_slice)) => {
+                    if let Some(last) = retrieved_slice.last() {
+                        let new_prefix = op(last, &increment);
+                        s.spawn(move |_| update(retrieved_slice, increment, op));
+                        Some(new_prefix)
+                    } else {
+                        Some(increment)
+                    }
                 }
-                Some(current_slice)
+                (None, Some(retrieved_slice)) => retrieved_slice.last().cloned(),
+                (previous, None) => previous,
             },
-        );
+        )
+    });
+}
+
+/// Sequential prefix on given slice, starting from given previous prefix (if any).
+/// Return the last prefix.
+fn local_prefix<T, O>(slice: &mut [T], previous: Option<T>, op: &O) -> Option<T>
+where
+    T: Clone,
+    O: Fn(&T, &T) -> T,
+{
+    slice.iter_mut().fold(previous, |previous, e| {
+        if let Some(c) = previous {
+            *e = op(e, &c);
+        }
+        Some(e.clone())
+    })
 }
 
 fn update<T, O>(slice: &mut [T], increment: T, op: &O)
@@ -58,3 +88,174 @@ where
 {
     slice.into_adapt_iter().for_each(|e| *e = op(e, &increment))
 }
+
+/// What the first phase of `collect_prefix` works on:
+/// elements left to scan and where to store their prefixes.
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(IndexedPower)]
+struct ScanInput<'a, I: AdaptiveIndexedIterator>
+where
+    I::Item: Send + Sync,
+{
+    input: I,
+    slots: EdibleSliceMut<'a, MaybeUninit<I::Item>>,
+}
+
+/// Collect prefixes of given iterator into a new vector.
+/// If we have an `init`ial value it comes first (and the last prefix is not computed).
+/// Like in `adaptive_prefix` each prefix is `op(element, previous_prefix)`.
+///
+/// Like `adaptive_prefix` we work in two phases:
+/// - prefixes are computed locally inside each block
+/// - each block (but the first) is updated with the last prefix of the block before
+pub(crate) fn collect_prefix<I, R, S, T, O>(runner: R, init: Option<T>, op: O) -> Vec<T>
+where
+    I: AdaptiveIndexedIterator<Item = T>,
+    R: AdaptiveRunner<I, S>,
+    S: Iterator<Item = usize>,
+    T: Clone + Send + Sync,
+    O: Fn(&T, &T) -> T + Sync,
+{
+    let (input, policy, sizes) = runner.input_policy_sizes();
+    let length = input.base_length();
+    if length == 0 {
+        return Vec::new();
+    }
+    let mut output = Vec::with_capacity(length);
+    {
+        let mut slots = EdibleSliceMut::new(&mut output.spare_capacity_mut()[..length]);
+        let input = match init {
+            Some(init) => {
+                // it is used as the previous prefix of the first element
+                slots.iter_mut().next().unwrap().write(init);
+                input.take(length - 1)
+            }
+            None => input,
+        };
+        ScanInput { input, slots }
+            .with_policy(policy)
+            .by_blocks(sizes)
+            .work(|mut scan, limit| {
+                let (todo, remaining) = scan.input.divide_at(limit);
+                let start = scan
+                    .slots
+                    .used_slice()
+                    .last()
+                    .map(|previous| unsafe { previous.assume_init_ref() }.clone());
+                let mut previous = start.as_ref();
+                for (element, slot) in todo.into_iter().zip(scan.slots.iter_mut()) {
+                    let prefix = match previous {
+                        Some(previous) => op(&element, previous),
+                        None => element,
+                    };
+                    previous = Some(slot.write(prefix));
+                }
+                ScanInput {
+                    input: remaining,
+                    slots: scan.slots,
+                }
+            })
+            // all slots are now initialized
+            .map(|scan| {
+                let slots = scan.slots.slice();
+                unsafe { slice::from_raw_parts_mut(slots.as_mut_ptr() as *mut T, slots.len()) }
+            })
+            .into_iter()
+            .fold(
+                None,
+                |potential_previous_slice: Option<&mut [T]>, current_slice| {
+                    if let Some(previous_slice) = potential_previous_slice {
+                        let increment = previous_slice.last().cloned().unwrap();
+                        current_slice
+                            .into_adapt_iter()
+                            .for_each(|e| *e = op(e, &increment));
+                    }
+                    Some(current_slice)
+                },
+            );
+    }
+    unsafe {
+        output.set_len(length);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::adaptive_prefix;
+    use crate::prelude::*;
+    use crate::Policy;
+    use rayon::ThreadPoolBuilder;
+
+    fn policies() -> Vec<Policy> {
+        vec![
+            Policy::Sequential,
+            Policy::Rayon,
+            Policy::Join(3),
+            Policy::JoinContext(7),
+            Policy::DepJoin(5),
+            Policy::Adaptive(1, 10),
+            Policy::Adaptive(5, 1000),
+            Policy::DefaultPolicy,
+        ]
+    }
+
+    #[test]
+    fn collected_prefixes_put_new_elements_first() {
+        // string concatenation does not commute
+        let concat = |element: &String, previous: &String| format!("{}{}", element, previous);
+        for &size in &[0, 1, 2, 1000] {
+            let letters = || {
+                (0..size)
+                    .into_adapt_iter()
+                    .map(|i| ((i % 26) as u8 + b'a') as char)
+                    .map(|c| c.to_string())
+            };
+            let mut inclusive = Vec::new();
+            let mut exclusive = vec![">".to_string()];
+            for letter in letters() {
+                let previous = inclusive.last().cloned().unwrap_or_default();
+                inclusive.push(concat(&letter, &previous));
+                exclusive.push(concat(&letter, exclusive.last().unwrap()));
+            }
+            exclusive.truncate(size);
+            for policy in policies() {
+                assert_eq!(letters().with_policy(policy).prefix(concat), inclusive);
+                assert_eq!(
+                    letters()
+                        .with_policy(policy)
+                        .exclusive_prefix(">".to_string(), concat),
+                    exclusive
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn adaptive_prefixes_match_sequential_ones() {
+        // affine maps compositions do not commute
+        let compose = |e: &(u64, u64), previous: &(u64, u64)| {
+            (
+                e.0 * previous.0 % 1_000_003,
+                (e.0 * previous.1 + e.1) % 1_000_003,
+            )
+        };
+        // we always run the default policy but schedules change with the number of threads
+        for threads in 1..=4 {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            for &size in &[0, 1, 2, 3, 17, 1000, 100_000] {
+                let mut values: Vec<(u64, u64)> =
+                    (0..size as u64).map(|i| (i % 7 + 1, i % 11)).collect();
+                let mut expected = values.clone();
+                for i in 1..size {
+                    expected[i] = compose(&expected[i], &expected[i - 1]);
+                }
+                pool.install(|| adaptive_prefix(&mut values, compose));
+                assert_eq!(values, expected);
+            }
+        }
+    }
+}
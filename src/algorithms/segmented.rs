@@ -0,0 +1,258 @@
+//! Segmented prefix and reductions.
+//! Segments are given by flags: a segment starts on each `true` flag
+//! (and at the beginning).
+use crate::fuse_slices;
+use crate::prelude::*;
+use crate::traits::IndexedPower;
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
+
+/// Values and the flags telling where their segments start.
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(IndexedPower)]
+struct Segments<'a, T: Send + Sync> {
+    values: &'a mut [T],
+    flags: &'a [bool],
+}
+
+/// Run adaptive prefix algorithm on each segment of given slice.
+/// Each element is replaced by the fold with op of all elements since the start
+/// of its segment.
+/// Like in `adaptive_prefix` new elements come first: each prefix is
+/// `op(element, previous_prefix)`.
+/// It requires an associative operation.
+///
+/// Like `adaptive_prefix` the master thread computes prefixes from the start while
+/// helpers work on the parts they steal. Only the elements of a helped part
+/// before its first segment start need to be updated afterwards.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::adaptive_segmented_prefix;
+/// let mut v = vec![1u32; 100_000];
+/// let flags: Vec<bool> = (0..100_000).map(|i| i % 1000 == 0).collect();
+/// adaptive_segmented_prefix(&mut v, &flags, |e1, e2| e1 + e2);
+/// let counts: Vec<u32> = (0..100_000).map(|i| i % 1000 + 1).collect();
+/// assert_eq!(v, counts);
+/// // new elements come first
+/// let mut words: Vec<String> = ["a", "b", "c", "d"].iter().map(|w| w.to_string()).collect();
+/// adaptive_segmented_prefix(&mut words, &[true, false, true, false], |e, p| {
+///     format!("{}{}", e, p)
+/// });
+/// assert_eq!(words, ["a", "ba", "c", "dc"]);
+/// ```
+pub fn adaptive_segmented_prefix<T, O>(values: &mut [T], flags: &[bool], op: O)
+where
+    T: Send + Sync + Clone,
+    O: Fn(&T, &T) -> T + Sync,
+{
+    assert_eq!(values.len(), flags.len(), "we need one flag per value");
+    let op = &op;
+    rayon::scope(|s| {
+        Segments { values, flags }
+            .cutting_fold(
+                || None,
+                |possible_previous: Option<(&mut [T], usize)>, segments| {
+                    let previous_prefix = possible_previous
+                        .as_ref()
+                        .and_then(|(previous_values, _)| previous_values.last().cloned());
+                    local_segmented_prefix(segments.values, segments.flags, previous_prefix, op);
+                    // how many values still wait for the prefix before them
+                    let waiting = segments
+                        .flags
+                        .iter()
+                        .position(|&flag| flag)
+                        .unwrap_or(segments.flags.len());
+                    Some(match possible_previous {
+                        Some((previous_values, previous_waiting)) => {
+                            let fused_waiting = if previous_waiting == previous_values.len() {
+                                previous_waiting + waiting
+                            } else {
+                                previous_waiting
+                            };
+                            (fuse_slices(previous_values, segments.values), fused_waiting)
+                        }
+                        None => (segments.values, waiting),
+                    })
+                },
+            )
+            .helping_cutting_fold(
+                None,
+                |previous, segments| {
+                    local_segmented_prefix(segments.values, segments.flags, previous, op)
+                },
+                |previous, dirty| match (previous, dirty) {
+                    (Some(increment), Some((retrieved_values, waiting))) => {
+                        if let Some(last) = retrieved_values.last() {
+                            let new_prefix = if waiting == retrieved_values.len() {
+                                op(last, &increment)
+                            } else {
+                                last.clone()
+                            };
+                            let (waiting_values, _) = retrieved_values.split_at_mut(waiting);
+                            s.spawn(move |_| {
+                                waiting_values
+                                    .into_adapt_iter()
+                                    .for_each(|e| *e = op(e, &increment))
+                            });
+                            Some(new_prefix)
+                        } else {
+                            Some(increment)
+                        }
+                    }
+                    (None, Some((retrieved_values, _))) => retrieved_values.last().cloned(),
+                    (previous, None) => previous,
+                },
+            )
+    });
+}
+
+/// Sequential segmented prefix, starting from given previous prefix (if any).
+/// Return the last prefix.
+fn local_segmented_prefix<T, O>(
+    values: &mut [T],
+    flags: &[bool],
+    previous: Option<T>,
+    op: &O,
+) -> Option<T>
+where
+    T: Clone,
+    O: Fn(&T, &T) -> T,
+{
+    values
+        .iter_mut()
+        .zip(flags)
+        .fold(previous, |previous, (e, &flag)| {
+            if !flag {
+                if let Some(c) = previous {
+                    *e = op(e, &c);
+                }
+            }
+            Some(e.clone())
+        })
+}
+
+/// Folds of the segments of a block of values.
+/// The first one might continue a segment from the block before
+/// and the last one might continue in the block after.
+/// Like `PartialProducts` for the infix solvers, they fuse associatively.
+#[derive(Debug, Clone)]
+struct SegmentsFolds<T> {
+    folds: Vec<T>,
+    // do we start a new segment (or continue the one before) ?
+    starts_segment: bool,
+}
+
+impl<T: Clone> SegmentsFolds<T> {
+    fn new() -> Self {
+        SegmentsFolds {
+            folds: Vec::new(),
+            starts_segment: false,
+        }
+    }
+    fn push<O: Fn(&T, &T) -> T>(mut self, value: &T, flag: bool, op: &O) -> Self {
+        if self.folds.is_empty() {
+            self.starts_segment = flag;
+            self.folds.push(value.clone());
+        } else if flag {
+            self.folds.push(value.clone());
+        } else {
+            let last = self.folds.last_mut().unwrap();
+            *last = op(last, value);
+        }
+        self
+    }
+    fn fuse<O: Fn(&T, &T) -> T>(mut self, other: Self, op: &O) -> Self {
+        if self.folds.is_empty() {
+            return other;
+        }
+        let mut other_folds = other.folds.into_iter();
+        if !other.starts_segment {
+            if let Some(first) = other_folds.next() {
+                let last = self.folds.last_mut().unwrap();
+                *last = op(last, &first);
+            }
+        }
+        self.folds.extend(other_folds);
+        self
+    }
+}
+
+/// Reduce each segment of given slice with op.
+/// Return one value per segment.
+/// It requires an associative operation.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::segmented_reduce;
+/// let v: Vec<u32> = (0..100_000).collect();
+/// let flags: Vec<bool> = (0..100_000).map(|i| i % 1000 == 0).collect();
+/// let maxima = segmented_reduce(&v, &flags, |a, b| *a.max(b));
+/// assert_eq!(maxima, (1..=100).map(|s| s * 1000 - 1).collect::<Vec<u32>>());
+/// ```
+pub fn segmented_reduce<T, O>(values: &[T], flags: &[bool], op: O) -> Vec<T>
+where
+    T: Send + Sync + Clone,
+    O: Fn(&T, &T) -> T + Sync,
+{
+    assert_eq!(values.len(), flags.len(), "we need one flag per value");
+    values
+        .into_adapt_iter()
+        .zip(flags.into_adapt_iter())
+        .fold(SegmentsFolds::new, |folds, (value, &flag)| {
+            folds.push(value, flag, &op)
+        })
+        .reduce(|left, right| left.fuse(right, &op))
+        .folds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn random_flags(size: usize, segments_density: u32) -> Vec<bool> {
+        let mut rng = rand::thread_rng();
+        (0..size)
+            .map(|_| rng.gen_range(0, segments_density) == 0)
+            .collect()
+    }
+
+    #[test]
+    fn segmented_prefixes_match_sequential_ones() {
+        for &(size, density) in &[(0, 2), (1, 2), (10, 3), (10_000, 1), (100_000, 1000)] {
+            let flags = random_flags(size, density);
+            // affine maps compositions do not commute
+            let compose = |a: &(u64, u64), b: &(u64, u64)| {
+                (a.0 * b.0 % 1_000_003, (b.0 * a.1 + b.1) % 1_000_003)
+            };
+            let mut values: Vec<(u64, u64)> =
+                (0..size as u64).map(|i| (i % 7 + 1, i % 11)).collect();
+            let mut expected = values.clone();
+            for i in 1..size {
+                if !flags[i] {
+                    expected[i] = compose(&expected[i], &expected[i - 1]);
+                }
+            }
+            adaptive_segmented_prefix(&mut values, &flags, compose);
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn segmented_reductions_match_sequential_ones() {
+        for &(size, density) in &[(0, 2), (1, 2), (10, 3), (10_000, 1), (100_000, 1000)] {
+            let flags = random_flags(size, density);
+            let values: Vec<u64> = (0..size as u64).collect();
+            let mut expected = Vec::new();
+            for (i, &flag) in flags.iter().enumerate() {
+                if flag || i == 0 {
+                    expected.push(0);
+                }
+                *expected.last_mut().unwrap() += values[i];
+            }
+            assert_eq!(segmented_reduce(&values, &flags, |a, b| a + b), expected);
+        }
+    }
+}
@@ -42,3 +42,38 @@ impl<
         io
     }
 }
+
+/// Same as `AdaptiveIteratorFold` but the identity is a clone of a given value.
+#[must_use = "folders are lazy and do nothing unless consumed"]
+pub struct AdaptiveIteratorFoldWith<
+    I: AdaptiveIterator,
+    IO: Send + Sync + Clone,
+    F: Fn(IO, I::Item) -> IO + Send + Sync,
+> {
+    pub(crate) init: IO,
+    pub(crate) fold_op: F,
+    pub(crate) phantom: PhantomData<I>,
+}
+
+impl<I: AdaptiveIterator, IO: Send + Sync + Clone, F: Fn(IO, I::Item) -> IO + Send + Sync> Folder
+    for AdaptiveIteratorFoldWith<I, IO, F>
+{
+    type Input = I;
+    type IntermediateOutput = IO;
+    type Output = IO;
+    fn identity(&self) -> Self::IntermediateOutput {
+        self.init.clone()
+    }
+    fn fold(
+        &self,
+        io: Self::IntermediateOutput,
+        i: Self::Input,
+        limit: usize,
+    ) -> (Self::IntermediateOutput, Self::Input) {
+        let (todo, remaining) = i.divide_at(limit);
+        (todo.into_iter().fold(io, &self.fold_op), remaining)
+    }
+    fn to_output(&self, io: Self::IntermediateOutput, _i: Self::Input) -> Self::Output {
+        io
+    }
+}
@@ -0,0 +1,453 @@
+//! Bridges between rayon's parallel iterators and adaptive inputs.
+//!
+//! Rayon hands out its producers only inside a `ProducerCallback` so we cannot return
+//! them. Instead, the work to do on the adaptive side is given as an `AdaptiveJob`
+//! which gets called back with an `AdaptiveIndexedIterator`.
+//!
+//! The other way around, `AdaptiveIterator::into_par_iter` wraps adaptive iterators
+//! into rayon producers, splitting them with `divide` and `divide_at`.
+use crate::prelude::*;
+#[cfg(feature = "logs")]
+use crate::real_rayon::iter::plumbing::{
+    bridge, bridge_unindexed, Consumer, Folder, Producer, ProducerCallback, UnindexedConsumer,
+    UnindexedProducer,
+};
+#[cfg(feature = "logs")]
+use crate::real_rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use crate::traits::IndexedPower;
+use crate::Policy;
+#[cfg(not(feature = "logs"))]
+use rayon::iter::plumbing::{
+    bridge, bridge_unindexed, Consumer, Folder, Producer, ProducerCallback, UnindexedConsumer,
+    UnindexedProducer,
+};
+#[cfg(not(feature = "logs"))]
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use std::iter::repeat;
+
+/// A rayon `Producer` seen as a `DivisibleAtIndex` input.
+pub struct ProducerInput<P: Producer> {
+    producer: P,
+    len: usize,
+}
+
+// producers are only required to be `Send`.
+// we never access the producer through a shared reference (only `len` is read)
+// so sharing a `ProducerInput` between threads cannot touch the producer.
+unsafe impl<P: Producer> Sync for ProducerInput<P> {}
+
+impl<P: Producer> Divisible for ProducerInput<P> {
+    type Power = IndexedPower;
+    fn base_length(&self) -> usize {
+        self.len
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.len / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<P: Producer> DivisibleIntoBlocks for ProducerInput<P> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.producer.split_at(index);
+        (
+            ProducerInput {
+                producer: left,
+                len: index,
+            },
+            ProducerInput {
+                producer: right,
+                len: self.len - index,
+            },
+        )
+    }
+}
+
+impl<P: Producer> IntoIterator for ProducerInput<P> {
+    type Item = P::Item;
+    type IntoIter = P::IntoIter;
+    fn into_iter(self) -> Self::IntoIter {
+        Producer::into_iter(self.producer)
+    }
+}
+
+/// Some work to do on the adaptive side of the bridge.
+///
+/// # Example
+///
+/// ```
+/// use rayon::prelude::*;
+/// use rayon_adaptive::prelude::*;
+/// use rayon_adaptive::iter::bridge::AdaptiveJob;
+/// use rayon_adaptive::Policy;
+///
+/// struct Sum;
+/// impl AdaptiveJob<u64> for Sum {
+///     type Output = u64;
+///     fn run<I: AdaptiveIndexedIterator<Item = u64>>(self, input: I, policy: Policy) -> u64 {
+///         input.with_policy(policy).sum()
+///     }
+/// }
+///
+/// let v: Vec<u64> = (0..10_000).collect();
+/// let s = v
+///     .par_iter()
+///     .map(|x| 2 * x)
+///     .adaptive()
+///     .with_policy(Policy::Join(100))
+///     .run(Sum);
+/// assert_eq!(s, 9_999 * 10_000);
+/// ```
+pub trait AdaptiveJob<T> {
+    type Output;
+    /// Do the work on given input, scheduled with given policy.
+    fn run<I: AdaptiveIndexedIterator<Item = T>>(self, input: I, policy: Policy) -> Self::Output;
+}
+
+/// A rayon `IndexedParallelIterator` waiting to be processed adaptively.
+/// Obtained with `adaptive`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Bridge<P> {
+    base: P,
+    policy: Policy,
+}
+
+/// Turn rayon's indexed parallel iterators into adaptive inputs.
+pub trait AdaptiveParallelIterator: IndexedParallelIterator {
+    /// Process this parallel iterator with the adaptive schedulers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use rayon_adaptive::prelude::*;
+    ///
+    /// let v: Vec<u32> = (0..10_000).collect();
+    /// let found = v.par_iter().map(|x| x * 3).adaptive().find_first(|x| x % 7 == 6);
+    /// assert_eq!(found, Some(6));
+    /// ```
+    fn adaptive(self) -> Bridge<Self> {
+        Bridge {
+            base: self,
+            policy: Default::default(),
+        }
+    }
+}
+
+impl<P: IndexedParallelIterator> AdaptiveParallelIterator for P {}
+
+struct JobCallback<J> {
+    job: J,
+    len: usize,
+    policy: Policy,
+}
+
+impl<T: Send, J: AdaptiveJob<T>> ProducerCallback<T> for JobCallback<J> {
+    type Output = J::Output;
+    fn callback<P: Producer<Item = T>>(self, producer: P) -> Self::Output {
+        let input = ProducerInput {
+            producer,
+            len: self.len,
+        };
+        self.job.run(input.into_adapt_iter(), self.policy)
+    }
+}
+
+impl<P: IndexedParallelIterator> Bridge<P> {
+    /// Choose the scheduling policy.
+    pub fn with_policy(self, policy: Policy) -> Self {
+        Bridge {
+            base: self.base,
+            policy,
+        }
+    }
+    /// Run given job on the adaptive input.
+    pub fn run<J: AdaptiveJob<P::Item>>(self, job: J) -> J::Output {
+        let len = self.base.len();
+        self.base.with_producer(JobCallback {
+            job,
+            len,
+            policy: self.policy,
+        })
+    }
+    /// Search for the first element matching given predicate.
+    pub fn find_first<F>(self, predicate: F) -> Option<P::Item>
+    where
+        F: Fn(&P::Item) -> bool + Sync + Send,
+        P::Item: Sync,
+    {
+        struct FindFirst<F>(F);
+        impl<T: Send + Sync, F: Fn(&T) -> bool + Sync + Send> AdaptiveJob<T> for FindFirst<F> {
+            type Output = Option<T>;
+            fn run<I: AdaptiveIndexedIterator<Item = T>>(
+                self,
+                input: I,
+                policy: Policy,
+            ) -> Option<T> {
+                input.with_policy(policy).find_first(self.0)
+            }
+        }
+        self.run(FindFirst(predicate))
+    }
+    /// Search for any element matching given predicate.
+    pub fn find_any<F>(self, predicate: F) -> Option<P::Item>
+    where
+        F: Fn(&P::Item) -> bool + Sync + Send,
+        P::Item: Sync,
+    {
+        struct FindAny<F>(F);
+        impl<T: Send + Sync, F: Fn(&T) -> bool + Sync + Send> AdaptiveJob<T> for FindAny<F> {
+            type Output = Option<T>;
+            fn run<I: AdaptiveIndexedIterator<Item = T>>(
+                self,
+                input: I,
+                policy: Policy,
+            ) -> Option<T> {
+                input.with_policy(policy).find_any(self.0)
+            }
+        }
+        self.run(FindAny(predicate))
+    }
+    /// Call given closure on each element, in parallel.
+    pub fn for_each<F>(self, op: F)
+    where
+        F: Fn(P::Item) + Sync + Send,
+    {
+        struct ForEach<F>(F);
+        impl<T: Send, F: Fn(T) + Sync + Send> AdaptiveJob<T> for ForEach<F> {
+            type Output = ();
+            fn run<I: AdaptiveIndexedIterator<Item = T>>(self, input: I, policy: Policy) {
+                input.with_policy(policy).for_each(self.0)
+            }
+        }
+        self.run(ForEach(op))
+    }
+    /// Call given closure sequentially on all elements, in order.
+    /// Idle threads help by producing the next elements in advance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use rayon_adaptive::prelude::*;
+    /// use std::sync::Mutex;
+    ///
+    /// let v: Vec<u32> = (0..10_000).collect();
+    /// let seen = Mutex::new(Vec::new());
+    /// v.par_iter()
+    ///     .map(|x| x + 1)
+    ///     .adaptive()
+    ///     .helping_for_each(|x| seen.lock().unwrap().push(x));
+    /// assert_eq!(seen.into_inner().unwrap(), (1..10_001).collect::<Vec<u32>>());
+    /// ```
+    pub fn helping_for_each<F>(self, op: F)
+    where
+        F: Fn(P::Item) + Sync + Send,
+        P::Item: Sync,
+    {
+        struct HelpingForEach<F>(F);
+        impl<T: Send + Sync, F: Fn(T) + Sync + Send> AdaptiveJob<T> for HelpingForEach<F> {
+            type Output = ();
+            fn run<I: AdaptiveIndexedIterator<Item = T>>(self, input: I, policy: Policy) {
+                let op = &self.0;
+                // helpers buffer what they produce, the master consumes it in order
+                input
+                    .with_policy(policy)
+                    .by_blocks(repeat(HELPING_BLOCK_SIZE))
+                    .partial_fold(Vec::new, |mut v, i, limit| {
+                        let (todo, remaining) = i.divide_at(limit);
+                        v.extend(todo);
+                        (v, remaining)
+                    })
+                    .helping_for_each(op, |v| v.into_iter().for_each(op))
+            }
+        }
+        match self.policy {
+            Policy::DefaultPolicy | Policy::Adaptive(_, _) => self.run(HelpingForEach(op)),
+            // only adaptive policies can help, fall back to a sequential loop
+            _ => self.base.with_producer(SequentialForEach(op)),
+        }
+    }
+}
+
+// size of the blocks processed by the master in `helping_for_each`.
+const HELPING_BLOCK_SIZE: usize = 1_000;
+
+struct SequentialForEach<F>(F);
+
+impl<T, F: Fn(T)> ProducerCallback<T> for SequentialForEach<F> {
+    type Output = ();
+    fn callback<P: Producer<Item = T>>(self, producer: P) {
+        Producer::into_iter(producer).for_each(self.0)
+    }
+}
+
+/// Rayon parallel iterator over an adaptive iterator.
+/// Obtained with `into_par_iter`.
+///
+/// It is an `IndexedParallelIterator` when the adaptive iterator is indexed
+/// and its sequential iterators are double ended and of known sizes.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ParIter<I> {
+    pub(crate) base: I,
+}
+
+impl<I> ParallelIterator for ParIter<I>
+where
+    I: AdaptiveIterator,
+    I::Item: Send,
+{
+    type Item = I::Item;
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge_unindexed(self, consumer)
+    }
+}
+
+impl<I> UnindexedProducer for ParIter<I>
+where
+    I: AdaptiveIterator,
+{
+    type Item = I::Item;
+    fn split(self) -> (Self, Option<Self>) {
+        if self.base.base_length() <= 1 {
+            (self, None)
+        } else {
+            let (left, right) = self.base.divide();
+            (ParIter { base: left }, Some(ParIter { base: right }))
+        }
+    }
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        folder.consume_iter(self.base)
+    }
+}
+
+impl<I> IndexedParallelIterator for ParIter<I>
+where
+    I: AdaptiveIndexedIterator,
+    I::Item: Send,
+    I::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn len(&self) -> usize {
+        self.base.base_length()
+    }
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(AdaptiveProducer { base: self.base })
+    }
+}
+
+// rayon producer dividing an indexed adaptive iterator at given indices.
+struct AdaptiveProducer<I> {
+    base: I,
+}
+
+impl<I> Producer for AdaptiveProducer<I>
+where
+    I: AdaptiveIndexedIterator,
+    I::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    type Item = I::Item;
+    type IntoIter = I::IntoIter;
+    fn into_iter(self) -> Self::IntoIter {
+        self.base.into_iter()
+    }
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.base.divide_at(index);
+        (
+            AdaptiveProducer { base: left },
+            AdaptiveProducer { base: right },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::Policy;
+    use rayon::prelude::*;
+    use std::sync::Mutex;
+
+    fn policies() -> Vec<Policy> {
+        vec![
+            Policy::Sequential,
+            Policy::Rayon,
+            Policy::Join(1),
+            Policy::JoinContext(3),
+            Policy::JoinRatio(2, 1, 3),
+            Policy::DepJoin(1),
+            Policy::Adaptive(1, 10),
+            Policy::Adaptive(100, 1000),
+            Policy::DefaultPolicy,
+        ]
+    }
+
+    #[test]
+    fn find_first_returns_the_leftmost_match() {
+        let v: Vec<usize> = (0..10_000).collect();
+        for policy in policies() {
+            for target in [0, 1, 999, 5_000, 9_999, 10_000] {
+                // all multiples of target match, only the first one is expected
+                let found = v
+                    .par_iter()
+                    .map(|x| x * 2)
+                    .adaptive()
+                    .with_policy(policy)
+                    .find_first(|&x| x >= 2 * target && x % (2 * target.max(1)) == 0);
+                assert_eq!(found, Some(2 * target).filter(|&x| x < 20_000));
+            }
+        }
+    }
+
+    #[test]
+    fn helping_for_each_processes_elements_in_order() {
+        for size in [0, 1, 999, 1_000, 1_001, 1_999, 2_000, 2_001, 10_000] {
+            let v: Vec<usize> = (0..size).collect();
+            for policy in policies() {
+                let seen = Mutex::new(Vec::new());
+                v.par_iter()
+                    .map(|x| x + 1)
+                    .adaptive()
+                    .with_policy(policy)
+                    .helping_for_each(|x| seen.lock().unwrap().push(x));
+                assert!(seen.into_inner().unwrap().into_iter().eq(1..=size));
+            }
+        }
+    }
+
+    #[test]
+    fn indexed_par_iters_keep_order() {
+        for size in [0, 1, 2, 1000, 10_000] {
+            for max_len in [1, 7, usize::MAX] {
+                let squares: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .map(|x| x * x)
+                    .into_par_iter()
+                    .with_max_len(max_len)
+                    .collect();
+                assert!(squares.iter().copied().eq((0..size).map(|x| x * x)));
+                let pairs: Vec<(usize, (usize, usize))> = (0..size)
+                    .into_adapt_iter()
+                    .map(|x| x * x)
+                    .into_par_iter()
+                    .zip((0..size).into_adapt_iter().rev().into_par_iter())
+                    .enumerate()
+                    .with_max_len(max_len)
+                    .collect();
+                assert!(pairs
+                    .into_iter()
+                    .eq((0..size).map(|x| x * x).zip((0..size).rev()).enumerate()));
+            }
+        }
+    }
+}
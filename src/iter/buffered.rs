@@ -0,0 +1,269 @@
+//! We provide here `BufferedIterInput` for working on sequential sources
+//! (channels, readers, generators) which cannot be split.
+use crate::policy::ParametrizedInput;
+use crate::prelude::*;
+use crate::traits::BlockedPower;
+use crate::Policy;
+use std::collections::vec_deque;
+use std::collections::VecDeque;
+use std::iter::{Chain, Empty, Flatten};
+use std::option;
+use std::sync::Mutex;
+
+/// Divisible input on a sequential iterator.
+///
+/// Elements are pulled into a local buffer when asked for.
+/// As long as the source is not exhausted its length is only an upper bound
+/// (the iterator's `size_hint`), so we only have a `BlockedPower`.
+///
+/// Dividing a live input pulls the next batch into the left part
+/// while the right part keeps the source: whoever steals takes over the reading.
+/// Elements are therefore kept in order.
+///
+/// Since our length is only an upper bound (`usize::MAX` for channels)
+/// the default policy would ask for absurdly large blocks and join policies
+/// would recurse on each batch. We are therefore only built together with
+/// an adaptive policy with explicit block sizes.
+/// Adaptors (`map`, `filter`...) go in the folding closures.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::prelude::*;
+/// use rayon_adaptive::BufferedIterInput;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::mpsc::channel;
+/// use std::thread;
+///
+/// let (sender, receiver) = channel();
+/// let producer = thread::spawn(move || {
+///     for i in 0..10_000usize {
+///         sender.send(i).unwrap();
+///     }
+/// });
+/// let sum = AtomicUsize::new(0);
+/// let add = |x: usize| {
+///     sum.fetch_add(x, Ordering::Relaxed);
+/// };
+/// BufferedIterInput::adaptive(receiver.into_iter(), 100, 10, 100)
+///     .partial_fold(Vec::new, |mut v, i, limit| {
+///         let (todo, remaining) = i.divide_at(limit);
+///         v.extend(todo);
+///         (v, remaining)
+///     })
+///     .helping_for_each(add, |v| v.into_iter().for_each(add));
+/// producer.join().unwrap();
+/// assert_eq!(sum.into_inner(), 5000 * 9999);
+/// ```
+pub struct BufferedIterInput<I: Iterator> {
+    buffer: VecDeque<I::Item>,
+    // we never lock it, it's only here to be `Sync`.
+    // `None` once exhausted.
+    iterator: Option<Mutex<I>>,
+    // upper bound on what is left in the iterator
+    remaining: usize,
+    batch_size: usize,
+}
+
+impl<I> BufferedIterInput<I>
+where
+    I: Iterator + Send,
+    I::Item: Send + Sync,
+{
+    /// Buffer given iterator, giving away batches of `batch_size` elements
+    /// when divided.
+    /// We are scheduled adaptively with blocks between `min_block_size`
+    /// and `max_block_size` elements.
+    pub fn adaptive(
+        iterator: I,
+        batch_size: usize,
+        min_block_size: usize,
+        max_block_size: usize,
+    ) -> ParametrizedInput<Self, Empty<usize>> {
+        BufferedIterInput::new(iterator, batch_size)
+            .with_policy(Policy::Adaptive(min_block_size, max_block_size))
+    }
+}
+
+impl<I: Iterator> BufferedIterInput<I> {
+    fn new(iterator: I, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batches cannot be empty");
+        let remaining = iterator.size_hint().1.unwrap_or(usize::MAX);
+        BufferedIterInput {
+            buffer: VecDeque::new(),
+            iterator: if remaining == 0 {
+                None
+            } else {
+                Some(Mutex::new(iterator))
+            },
+            remaining,
+            batch_size,
+        }
+    }
+    /// Input on already buffered elements.
+    fn batch(buffer: VecDeque<I::Item>, batch_size: usize) -> Self {
+        BufferedIterInput {
+            buffer,
+            iterator: None,
+            remaining: 0,
+            batch_size,
+        }
+    }
+    /// Pull from the iterator until we have `size` elements buffered
+    /// (or until it is exhausted).
+    fn fill(&mut self, size: usize) {
+        if let Some(mutex) = self.iterator.as_mut() {
+            let iterator = mutex.get_mut().unwrap();
+            let missing = size.saturating_sub(self.buffer.len());
+            let old_length = self.buffer.len();
+            self.buffer.extend(iterator.take(missing));
+            let pulled = self.buffer.len() - old_length;
+            if pulled < missing {
+                self.iterator = None;
+                self.remaining = 0;
+            } else {
+                self.remaining = self.remaining.saturating_sub(pulled);
+                if self.remaining == 0 {
+                    self.iterator = None;
+                }
+            }
+        }
+    }
+}
+
+impl<I> Divisible for BufferedIterInput<I>
+where
+    I: Iterator + Send,
+    I::Item: Send + Sync,
+{
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.buffer.len().saturating_add(self.remaining)
+    }
+    fn divide(mut self) -> (Self, Self) {
+        if self.iterator.is_some() {
+            // buffered elements come first: keep them on the left
+            // and give away the source
+            self.fill(self.batch_size);
+            let batch = std::mem::take(&mut self.buffer);
+            (BufferedIterInput::batch(batch, self.batch_size), self)
+        } else {
+            let mid = self.buffer.len() / 2;
+            self.divide_at(mid)
+        }
+    }
+}
+
+impl<I> DivisibleIntoBlocks for BufferedIterInput<I>
+where
+    I: Iterator + Send,
+    I::Item: Send + Sync,
+{
+    fn divide_at(mut self, index: usize) -> (Self, Self) {
+        if index >= self.base_length() {
+            // no need to read anything
+            let batch_size = self.batch_size;
+            return (self, BufferedIterInput::batch(VecDeque::new(), batch_size));
+        }
+        self.fill(index);
+        let index = std::cmp::min(index, self.buffer.len());
+        // only move the smallest part
+        let left_buffer = if 2 * index <= self.buffer.len() {
+            self.buffer.drain(..index).collect()
+        } else {
+            let right_buffer = self.buffer.split_off(index);
+            std::mem::replace(&mut self.buffer, right_buffer)
+        };
+        (BufferedIterInput::batch(left_buffer, self.batch_size), self)
+    }
+}
+
+impl<I: Iterator> IntoIterator for BufferedIterInput<I> {
+    type Item = I::Item;
+    type IntoIter = Chain<vec_deque::IntoIter<I::Item>, Flatten<option::IntoIter<I>>>;
+    fn into_iter(self) -> Self::IntoIter {
+        let iterator = self.iterator.map(|mutex| mutex.into_inner().unwrap());
+        self.buffer
+            .into_iter()
+            .chain(iterator.into_iter().flatten())
+    }
+}
+
+impl<I> AdaptiveIterator for BufferedIterInput<I>
+where
+    I: Iterator + Send,
+    I::Item: Send + Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::ThreadPoolBuilder;
+    use std::sync::mpsc::channel;
+    use std::sync::Mutex;
+    use std::thread;
+
+    #[test]
+    fn all_elements_are_processed_in_order() {
+        // steals only happen with several threads
+        let pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        pool.install(check_all_elements);
+    }
+
+    fn check_all_elements() {
+        for &(size, batch_size) in &[(0, 1), (1, 1), (7, 3), (10_000, 100), (100_000, 17)] {
+            for &(min_block_size, max_block_size) in &[(1, 10), (10, 1000)] {
+                // unknown size
+                let (sender, receiver) = channel();
+                let producer =
+                    thread::spawn(move || (0..size).for_each(|i| sender.send(i).unwrap()));
+                let seen = Mutex::new(Vec::new());
+                let push = |x: usize| seen.lock().unwrap().push(x);
+                BufferedIterInput::adaptive(
+                    receiver.into_iter(),
+                    batch_size,
+                    min_block_size,
+                    max_block_size,
+                )
+                .partial_fold(Vec::new, |mut v, i, limit| {
+                    let (todo, remaining) = i.divide_at(limit);
+                    v.extend(todo);
+                    (v, remaining)
+                })
+                .helping_for_each(push, |v| v.into_iter().for_each(push));
+                producer.join().unwrap();
+                assert!(seen.into_inner().unwrap().into_iter().eq(0..size));
+                // collecting does not allocate for the whole upper bound
+                let (sender, receiver) = channel();
+                (0..size).for_each(|i| sender.send(i).unwrap());
+                drop(sender);
+                let collected: Vec<usize> = BufferedIterInput::adaptive(
+                    receiver.into_iter(),
+                    batch_size,
+                    min_block_size,
+                    max_block_size,
+                )
+                .collect();
+                assert!(collected.into_iter().eq(0..size));
+                // known size, other runners
+                let total: usize = BufferedIterInput::adaptive(
+                    0..size,
+                    batch_size,
+                    min_block_size,
+                    max_block_size,
+                )
+                .sum();
+                assert_eq!(total, (0..size).sum());
+                let collected: Vec<usize> = BufferedIterInput::adaptive(
+                    0..size,
+                    batch_size,
+                    min_block_size,
+                    max_block_size,
+                )
+                .collect();
+                assert!(collected.into_iter().eq(0..size));
+            }
+        }
+    }
+}
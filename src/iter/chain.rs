@@ -0,0 +1,79 @@
+use crate::prelude::*;
+use std::iter;
+
+/// Iterator adaptor going through two iterators one after the other.
+/// Both iterators must have the same `Power`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Chain<A: AdaptiveIterator, B: AdaptiveIterator<Power = A::Power>> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+}
+
+impl<A: AdaptiveIterator, B: AdaptiveIterator<Power = A::Power>> Divisible for Chain<A, B> {
+    type Power = A::Power;
+    fn base_length(&self) -> usize {
+        self.a.base_length() + self.b.base_length()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<A: AdaptiveIterator, B: AdaptiveIterator<Power = A::Power>> DivisibleIntoBlocks
+    for Chain<A, B>
+{
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let a_len = self.a.base_length();
+        if index <= a_len {
+            let (left_a, right_a) = self.a.divide_at(index);
+            let (empty_b, b) = self.b.divide_at(0);
+            (
+                Chain {
+                    a: left_a,
+                    b: empty_b,
+                },
+                Chain { a: right_a, b },
+            )
+        } else {
+            let (a, empty_a) = self.a.divide_at(a_len);
+            let (left_b, right_b) = self.b.divide_at(index - a_len);
+            (
+                Chain { a, b: left_b },
+                Chain {
+                    a: empty_a,
+                    b: right_b,
+                },
+            )
+        }
+    }
+}
+
+impl<A, B> IntoIterator for Chain<A, B>
+where
+    A: AdaptiveIterator,
+    B: AdaptiveIterator<Power = A::Power, Item = A::Item>,
+{
+    type Item = A::Item;
+    type IntoIter = iter::Chain<A::IntoIter, B::IntoIter>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.a.into_iter().chain(self.b)
+    }
+}
+
+impl<A, B> AdaptiveIterator for Chain<A, B>
+where
+    A: AdaptiveIterator,
+    B: AdaptiveIterator<Power = A::Power, Item = A::Item>,
+{
+}
+
+impl<A, B> AdaptiveIndexedIterator for Chain<A, B>
+where
+    A: AdaptiveIndexedIterator,
+    B: AdaptiveIndexedIterator<Item = A::Item>,
+{
+}
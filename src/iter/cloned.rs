@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use derive_divisible::{Divisible, DivisibleIntoBlocks};
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
 use std::iter;
 
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
@@ -39,8 +39,6 @@ where
     }
 }
 
-impl<I: AdaptiveIndexedIterator> DivisibleAtIndex for Cloned<I> {}
-
 impl<'a, T: Clone + 'a, I: AdaptiveIterator<Item = &'a T>> AdaptiveIterator for Cloned<I> {}
 impl<'a, T: Clone + 'a, I: AdaptiveIndexedIterator<Item = &'a T>> AdaptiveIndexedIterator
     for Cloned<I>
@@ -1,8 +1,56 @@
+use crate::activated_input::ActivatedInput;
+use crate::folders::fold::Fold;
 use crate::prelude::*;
-use crate::traits::BlockedPower;
-use rayon::current_num_threads;
-use std::iter::repeat;
-use std::mem;
+use crate::traits::{BlockedPower, IndexedPower};
+use crate::{fuse_slices, EdibleSliceMut, Policy};
+use std::cmp::min;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList};
+use std::hash::{BuildHasher, Hash};
+use std::iter::{empty, once};
+use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
+
+/// Collections which can be built from any `AdaptiveIterator`.
+/// Implement it for your own collections and build them with
+/// `FromAdaptiveIterator::from_adapt_iter`.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::prelude::*;
+/// use std::collections::LinkedList;
+///
+/// struct Histogram([usize; 10]);
+///
+/// impl FromAdaptiveIterator<usize> for Histogram {
+///     fn from_adapt_iter<I, R, S>(runner: R) -> Self
+///     where
+///         I: AdaptiveIterator<Item = usize>,
+///         R: AdaptiveRunner<I, S>,
+///         S: Iterator<Item = usize>,
+///     {
+///         // go through a linked list of blocks
+///         let blocks: LinkedList<usize> = FromAdaptiveIterator::from_adapt_iter(runner);
+///         let mut counts = [0; 10];
+///         blocks.into_iter().for_each(|e| counts[e] += 1);
+///         Histogram(counts)
+///     }
+/// }
+///
+/// let h: Histogram = FromAdaptiveIterator::from_adapt_iter((0..1000).into_adapt_iter().map(|e| e % 10));
+/// assert!(h.0.iter().all(|&c| c == 100));
+/// ```
+pub trait FromAdaptiveIterator<T>
+where
+    T: Send,
+{
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = T>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>;
+}
+
 pub trait FromAdaptiveBlockedIterator<T>
 where
     T: Send,
@@ -25,45 +73,343 @@ where
         S: Iterator<Item = usize>;
 }
 
-//TODO:
-// 1) we need to test performances for block sizes
-// 2) we still need the fully adaptive algorithm
-// 3) extend in parallel ?
-impl<T: Send + Sync> FromAdaptiveBlockedIterator<T> for Vec<T> {
-    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+/// Extend a collection in parallel.
+/// `P` is the power of the iterators we extend from: each power comes with its own algorithm.
+pub trait AdaptiveExtend<T, P>
+where
+    T: Send,
+{
+    fn adapt_extend<I, R, S>(&mut self, runner: R)
+    where
+        I: AdaptiveIterator<Item = T, Power = P>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>;
+}
+
+/// Indexed iterators write directly into the spare capacity:
+/// no data gets moved twice.
+/// Each sequential fold keeps track of the slots it initialized so that
+/// if anything panics only these get dropped.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::prelude::*;
+/// let mut v: Vec<usize> = (0..10).collect();
+/// v.adapt_extend((10..1000).into_adapt_iter());
+/// assert_eq!(v, (0..1000).collect::<Vec<_>>());
+/// ```
+impl<T: Send + Sync> AdaptiveExtend<T, IndexedPower> for Vec<T> {
+    fn adapt_extend<I, R, S>(&mut self, runner: R)
+    where
+        I: AdaptiveIterator<Item = T, Power = IndexedPower>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        let (input, policy, sizes) = runner.input_policy_sizes();
+        let extra_len = input.base_length();
+        let old_len = self.len();
+        self.reserve(extra_len);
+        let slots = &mut self.spare_capacity_mut()[..extra_len];
+        let initialized = ActivatedInput {
+            input: SlottedInput { input, slots },
+            folder: Fold {
+                identity_op: || None,
+                fold_op: initialize_block,
+                phantom: PhantomData,
+            },
+            policy,
+            sizes,
+            power: PhantomData,
+        }
+        .reduce(fuse_initialized);
+        let written = initialized.map(InitializedSlots::release).unwrap_or(0);
+        assert_eq!(
+            written, extra_len,
+            "indexed iterator produced a wrong number of elements"
+        );
+        unsafe { self.set_len(old_len + extra_len) }
+    }
+}
+
+/// Slots initialized by a sequential fold (all initialized slots are at the beginning).
+/// On drop (only when unwinding) initialized elements are dropped.
+struct InitializedSlots<'a, T> {
+    slots: &'a mut [MaybeUninit<T>],
+    initialized: usize,
+}
+
+impl<'a, T> InitializedSlots<'a, T> {
+    /// Add more (contiguous) slots at the end.
+    fn append_slots(mut self, slots: &'a mut [MaybeUninit<T>]) -> Self {
+        let previous_slots = mem::take(&mut self.slots);
+        self.slots = fuse_slices(previous_slots, slots);
+        self
+    }
+    fn push(&mut self, element: T) {
+        self.slots[self.initialized].write(element);
+        self.initialized += 1;
+    }
+    /// Fuse with next initialized slots.
+    fn fuse(mut self, mut other: Self) -> Self {
+        debug_assert_eq!(self.initialized, self.slots.len());
+        let other_initialized = mem::replace(&mut other.initialized, 0);
+        self = self.append_slots(mem::take(&mut other.slots));
+        self.initialized += other_initialized;
+        self
+    }
+    /// Everything went fine: the owner of the slots is now in charge of the elements.
+    /// Return how many elements we initialized.
+    fn release(mut self) -> usize {
+        mem::replace(&mut self.initialized, 0)
+    }
+}
+
+impl<'a, T> Drop for InitializedSlots<'a, T> {
+    fn drop(&mut self) {
+        for slot in &mut self.slots[..self.initialized] {
+            unsafe { slot.assume_init_drop() }
+        }
+    }
+}
+
+fn initialize_block<'a, I, T>(
+    initialized: Option<InitializedSlots<'a, T>>,
+    input: SlottedInput<'a, I, T>,
+    limit: usize,
+) -> (Option<InitializedSlots<'a, T>>, SlottedInput<'a, I, T>)
+where
+    I: AdaptiveIterator<Item = T>,
+    T: Send + Sync,
+{
+    let (todo, remaining) = input.divide_at(limit);
+    let mut initialized = match initialized {
+        Some(initialized) => initialized.append_slots(todo.slots),
+        None => InitializedSlots {
+            slots: todo.slots,
+            initialized: 0,
+        },
+    };
+    todo.input
+        .into_iter()
+        .for_each(|element| initialized.push(element));
+    (Some(initialized), remaining)
+}
+
+fn fuse_initialized<'a, T>(
+    left: Option<InitializedSlots<'a, T>>,
+    right: Option<InitializedSlots<'a, T>>,
+) -> Option<InitializedSlots<'a, T>> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left.fuse(right)),
+        (left, None) => left,
+        (None, right) => right,
+    }
+}
+
+/// Blocked iterators write directly into the spare capacity.
+/// Each part of the input gets one slot per unit of `base_length`
+/// and each sequential fold writes contiguously right after the previous one.
+/// Only stolen parts need to be moved back (with `EdibleSliceMut::fuse`) and
+/// with adaptive policies this compaction is done by the main thread while the others
+/// keep on filtering (see `helping_partial_fold`).
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::prelude::*;
+/// use rayon_adaptive::Policy;
+/// let mut v = Vec::with_capacity(1000);
+/// v.adapt_extend((0..1000).into_adapt_iter().filter(|&e| e % 2 == 0));
+/// v.adapt_extend(
+///     (0..1000)
+///         .into_adapt_iter()
+///         .filter(|&e| e % 2 == 1)
+///         .with_policy(Policy::Join(10)),
+/// );
+/// assert!(v.iter().cloned().eq((0..500).map(|e| 2 * e).chain((0..500).map(|e| 2 * e + 1))));
+/// ```
+impl<T: Send + Sync> AdaptiveExtend<T, BlockedPower> for Vec<T> {
+    fn adapt_extend<I, R, S>(&mut self, runner: R)
     where
         I: AdaptiveIterator<Item = T, Power = BlockedPower>,
-        R: AdaptiveBlockedIteratorRunner<I, S>,
+        R: AdaptiveRunner<I, S>,
         S: Iterator<Item = usize>,
     {
         let (input, policy, sizes) = runner.input_policy_sizes();
-        let capacity = input.base_length();
-        input
-            .with_policy(policy)
-            .by_blocks(sizes.chain(repeat(
-                // let's fit in 1mb cache
-                1_000_000 * current_num_threads() / mem::size_of::<T>(),
-            )))
-            .partial_fold(
-                move || Vec::with_capacity(capacity),
-                |mut v, i, limit| {
-                    let (todo, remaining) = i.divide_at(limit);
-                    v.extend(todo.into_iter()); // optimized extend, yay !
-                    (v, remaining)
+        let length = input.base_length();
+        if self.try_reserve(length).is_err() {
+            // lengths are only upper bounds and this one is way too large
+            // (streams of unknown sizes for example): we cannot use slots.
+            let collected = extend_and_merge(
+                input.with_policy(policy).by_blocks(sizes),
+                |mut left: LinkedList<T>, mut right| {
+                    left.append(&mut right);
+                    left
                 },
-            )
-            .into_iter()
-            .fold(None, |final_v: Option<Vec<T>>, v| {
-                if final_v.is_some() {
-                    final_v.map(|mut f| {
-                        f.extend(v);
-                        f
-                    })
+            );
+            self.extend(collected);
+            return;
+        }
+        let old_len = self.len();
+        let slots = &mut self.spare_capacity_mut()[..length];
+        let collected = SlottedInput { input, slots }
+            .chunks(sizes.chain(once(length)))
+            .fold(None, |collected, chunk| {
+                let folder = Fold {
+                    identity_op: || None,
+                    fold_op: collect_block,
+                    phantom: PhantomData,
+                };
+                let activated_chunk = ActivatedInput {
+                    input: chunk,
+                    folder,
+                    policy,
+                    sizes: empty(),
+                    power: PhantomData,
+                };
+                match policy {
+                    Policy::DefaultPolicy | Policy::Adaptive(_, _) => activated_chunk
+                        .helping_partial_fold(collected, collect_block, fuse_collected),
+                    _ => fuse_collected(collected, activated_chunk.reduce(fuse_collected)),
+                }
+            });
+        if let Some(mut collected) = collected {
+            let written = collected.slice.used_slice().len();
+            let tail = collected.tail;
+            unsafe { self.set_len(old_len + written) }
+            self.extend(tail)
+        }
+    }
+}
+
+/// Input of the blocked collect: the iterator together with
+/// uninitialized slots for its elements (one slot per unit of `base_length`).
+struct SlottedInput<'a, I, T> {
+    input: I,
+    slots: &'a mut [MaybeUninit<T>],
+}
+
+impl<'a, I: AdaptiveIterator<Item = T>, T: Send + Sync> Divisible for SlottedInput<'a, I, T> {
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.input.base_length()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+}
+
+impl<'a, I: AdaptiveIterator<Item = T>, T: Send + Sync> DivisibleIntoBlocks
+    for SlottedInput<'a, I, T>
+{
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left_input, right_input) = self.input.divide_at(index);
+        // some inputs (flat maps, strings) do not keep their length when divided
+        // so we might run out of slots, or have some left when nothing remains.
+        // slots left must stay contiguous with the ones of the next block.
+        let slots_index = if right_input.base_length() == 0 {
+            self.slots.len()
+        } else {
+            min(index, self.slots.len())
+        };
+        let (left_slots, right_slots) = self.slots.split_at_mut(slots_index);
+        (
+            SlottedInput {
+                input: left_input,
+                slots: left_slots,
+            },
+            SlottedInput {
+                input: right_input,
+                slots: right_slots,
+            },
+        )
+    }
+}
+
+/// Elements collected by a sequential fold.
+/// They are stored at the beginning of `slice` and if we ever run out
+/// of slots (inputs producing more elements than their length) we go on in `tail`.
+struct Collected<'a, T> {
+    slice: EdibleSliceMut<'a, MaybeUninit<T>>,
+    tail: Vec<T>,
+}
+
+impl<'a, T: Send + Sync> Collected<'a, T> {
+    /// Add more (contiguous) slots at the end.
+    fn append_slots(self, slots: &'a mut [MaybeUninit<T>]) -> Self {
+        Collected {
+            slice: self.slice.fuse(EdibleSliceMut::new(slots)),
+            tail: self.tail,
+        }
+    }
+    fn extend<J: Iterator<Item = T>>(&mut self, mut elements: J) {
+        if self.tail.is_empty() {
+            let mut slots = self.slice.iter_mut();
+            // take each element before its slot in order not to lose slots
+            for element in elements.by_ref() {
+                if let Some(slot) = slots.next() {
+                    slot.write(element);
                 } else {
-                    Some(v)
+                    self.tail.push(element);
+                    break;
                 }
-            })
-            .unwrap_or_else(Vec::new)
+            }
+        }
+        self.tail.extend(elements)
+    }
+    /// Fuse with next collected elements, moving them back against ours.
+    fn fuse(mut self, mut other: Self) -> Self {
+        if !self.tail.is_empty() {
+            // other's elements go after our tail
+            let other_elements = other.slice.used_slice();
+            self.tail.extend(
+                other_elements
+                    .iter()
+                    .map(|element| unsafe { element.assume_init_read() }),
+            );
+            other.slice.rewind();
+        }
+        self.tail.append(&mut other.tail);
+        Collected {
+            slice: self.slice.fuse(other.slice),
+            tail: self.tail,
+        }
+    }
+}
+
+fn collect_block<'a, I, T>(
+    collected: Option<Collected<'a, T>>,
+    input: SlottedInput<'a, I, T>,
+    limit: usize,
+) -> (Option<Collected<'a, T>>, SlottedInput<'a, I, T>)
+where
+    I: AdaptiveIterator<Item = T>,
+    T: Send + Sync,
+{
+    let (todo, remaining) = input.divide_at(limit);
+    let mut collected = match collected {
+        Some(collected) => collected.append_slots(todo.slots),
+        None => Collected {
+            slice: EdibleSliceMut::new(todo.slots),
+            tail: Vec::new(),
+        },
+    };
+    collected.extend(todo.input.into_iter());
+    (Some(collected), remaining)
+}
+
+impl<T: Send + Sync> FromAdaptiveBlockedIterator<T> for Vec<T> {
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = T, Power = BlockedPower>,
+        R: AdaptiveBlockedIteratorRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        let mut output_vector = Vec::new();
+        output_vector.adapt_extend(runner);
+        output_vector
     }
 }
 
@@ -73,19 +419,450 @@ impl<T: Send + Sync> FromAdaptiveIndexedIterator<T> for Vec<T> {
         I: AdaptiveIndexedIterator<Item = T>,
         R: AdaptiveIndexedIteratorRunner<I, S>,
     {
-        let (input, policy, sizes) = runner.input_policy_sizes();
-        let output_len = input.base_length();
-        let mut output_vector = Vec::with_capacity(output_len);
-        unsafe {
-            output_vector.set_len(output_len);
+        let mut output_vector = Vec::new();
+        output_vector.adapt_extend(runner);
+        output_vector
+    }
+}
+
+/// Collecting for all types implementing `FromAdaptiveIterator`
+/// (whatever the iterator's power).
+macro_rules! collect_from_adaptive_iterator {
+    ($item: ty => $collection: ty, [$($generics: tt)*]) => {
+        impl<$($generics)*> FromAdaptiveBlockedIterator<$item> for $collection {
+            fn from_adapt_iter<I, R, S>(runner: R) -> Self
+            where
+                I: AdaptiveIterator<Item = $item, Power = BlockedPower>,
+                R: AdaptiveBlockedIteratorRunner<I, S>,
+                S: Iterator<Item = usize>,
+            {
+                FromAdaptiveIterator::from_adapt_iter(runner)
+            }
+        }
+
+        impl<$($generics)*> FromAdaptiveIndexedIterator<$item> for $collection {
+            fn from_adapt_iter<I, R, S>(runner: R) -> Self
+            where
+                I: AdaptiveIndexedIterator<Item = $item>,
+                R: AdaptiveIndexedIteratorRunner<I, S>,
+                S: Iterator<Item = usize>,
+            {
+                FromAdaptiveIterator::from_adapt_iter(runner)
+            }
         }
-        let output_slice: &mut [T] = &mut output_vector;
-        output_slice
-            .into_adapt_iter()
-            .zip(input)
+    };
+}
+
+/// Extend a collection for each block and merge the collections
+/// (in order) with given merge function.
+fn extend_and_merge<T, C, I, R, S, M>(runner: R, merge: M) -> C
+where
+    T: Send,
+    C: Default + Extend<T> + Send + Sync,
+    I: AdaptiveIterator<Item = T>,
+    R: AdaptiveRunner<I, S>,
+    S: Iterator<Item = usize>,
+    M: Fn(C, C) -> C + Sync,
+{
+    let (input, policy, sizes) = runner.input_policy_sizes();
+    input
+        .with_policy(policy)
+        .by_blocks(sizes)
+        .partial_fold(C::default, |mut c, i, limit| {
+            let (todo, remaining) = i.divide_at(limit);
+            c.extend(todo);
+            (c, remaining)
+        })
+        .reduce(merge)
+}
+
+impl<K, V, H> FromAdaptiveIterator<(K, V)> for HashMap<K, V, H>
+where
+    K: Eq + Hash + Send + Sync,
+    V: Send + Sync,
+    H: BuildHasher + Default + Send + Sync,
+{
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = (K, V)>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        // later values win like in sequential collect
+        extend_and_merge(runner, |mut left: Self, right| {
+            left.extend(right);
+            left
+        })
+    }
+}
+collect_from_adaptive_iterator!((K, V) => HashMap<K, V, H>, [K: Eq + Hash + Send + Sync, V: Send + Sync, H: BuildHasher + Default + Send + Sync]);
+
+impl<T, H> FromAdaptiveIterator<T> for HashSet<T, H>
+where
+    T: Eq + Hash + Send + Sync,
+    H: BuildHasher + Default + Send + Sync,
+{
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = T>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        extend_and_merge(runner, |mut left: Self, right| {
+            left.extend(right);
+            left
+        })
+    }
+}
+collect_from_adaptive_iterator!(T => HashSet<T, H>, [T: Eq + Hash + Send + Sync, H: BuildHasher + Default + Send + Sync]);
+
+impl<K: Ord + Send + Sync, V: Send + Sync> FromAdaptiveIterator<(K, V)> for BTreeMap<K, V> {
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = (K, V)>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        extend_and_merge(runner, |mut left: Self, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+collect_from_adaptive_iterator!((K, V) => BTreeMap<K, V>, [K: Ord + Send + Sync, V: Send + Sync]);
+
+impl<T: Ord + Send + Sync> FromAdaptiveIterator<T> for BTreeSet<T> {
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = T>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        extend_and_merge(runner, |mut left: Self, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+collect_from_adaptive_iterator!(T => BTreeSet<T>, [T: Ord + Send + Sync]);
+
+impl<T: Send + Sync> FromAdaptiveIterator<T> for LinkedList<T> {
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = T>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        extend_and_merge(runner, |mut left: Self, mut right| {
+            left.append(&mut right);
+            left
+        })
+    }
+}
+collect_from_adaptive_iterator!(T => LinkedList<T>, [T: Send + Sync]);
+
+impl FromAdaptiveIterator<char> for String {
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = char>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        extend_and_merge(runner, |mut left: Self, right| {
+            left.push_str(&right);
+            left
+        })
+    }
+}
+collect_from_adaptive_iterator!(char => String, []);
+
+impl<'a> FromAdaptiveIterator<&'a str> for String {
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = &'a str>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        extend_and_merge(runner, |mut left: Self, right| {
+            left.push_str(&right);
+            left
+        })
+    }
+}
+collect_from_adaptive_iterator!(&'a str => String, ['a]);
+
+/// Group elements into vectors, one for each sequential part of the computation.
+/// No data is moved after the fold.
+/// This is not available through `collect` since it would break type inference
+/// when collecting into `Vec<_>`: call `FromAdaptiveIterator::from_adapt_iter` instead.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::prelude::*;
+/// let groups: Vec<Vec<_>> = FromAdaptiveIterator::from_adapt_iter((0..1000).into_adapt_iter());
+/// assert!(groups.into_iter().flatten().eq(0..1000));
+/// ```
+impl<T: Send + Sync> FromAdaptiveIterator<T> for Vec<Vec<T>> {
+    fn from_adapt_iter<I, R, S>(runner: R) -> Self
+    where
+        I: AdaptiveIterator<Item = T>,
+        R: AdaptiveRunner<I, S>,
+        S: Iterator<Item = usize>,
+    {
+        let (input, policy, sizes) = runner.input_policy_sizes();
+        input
             .with_policy(policy)
             .by_blocks(sizes)
-            .for_each(|(out_ref, in_ref)| unsafe { std::ptr::write(out_ref, in_ref) });
-        output_vector
+            .partial_fold(Vec::new, |mut v, i, limit| {
+                let (todo, remaining) = i.divide_at(limit);
+                v.extend(todo);
+                (v, remaining)
+            })
+            .into_iter()
+            .filter(|v| !v.is_empty())
+            .collect()
+    }
+}
+
+fn fuse_collected<'a, T: Send + Sync>(
+    left: Option<Collected<'a, T>>,
+    right: Option<Collected<'a, T>>,
+) -> Option<Collected<'a, T>> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left.fuse(right)),
+        (left, None) => left,
+        (None, right) => right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::Policy;
+    use rayon::current_num_threads;
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList};
+    use std::panic;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Count drops in a counter of our own.
+    struct Counted {
+        value: String,
+        dropped: Arc<AtomicUsize>,
+    }
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            assert!(!self.value.is_empty());
+            self.dropped.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn indexed_collect_is_panic_safe() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let result = panic::catch_unwind(|| {
+            let v: Vec<Counted> = (0..100_000)
+                .into_adapt_iter()
+                .map(|i| {
+                    if i == 54_321 {
+                        panic!("failing on purpose");
+                    }
+                    created.fetch_add(1, Ordering::SeqCst);
+                    Counted {
+                        value: i.to_string(),
+                        dropped: dropped.clone(),
+                    }
+                })
+                .collect();
+            v
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            created.load(Ordering::SeqCst),
+            dropped.load(Ordering::SeqCst)
+        );
+        // it still works when nothing panics
+        let v: Vec<String> = (0..1000).into_adapt_iter().map(|i| i.to_string()).collect();
+        assert!(v.into_iter().eq((0..1000).map(|i| i.to_string())));
+    }
+
+    fn policies() -> Vec<Policy> {
+        vec![
+            Policy::Sequential,
+            Policy::Rayon,
+            Policy::Join(1),
+            Policy::JoinContext(3),
+            Policy::JoinRatio(2, 1, 3),
+            Policy::JoinContextRatio(2, 2, 5),
+            Policy::DepJoin(1),
+            Policy::Adaptive(1, 10),
+            Policy::Adaptive(100, 1000),
+            Policy::DefaultPolicy,
+        ]
+    }
+
+    #[test]
+    fn blocked_collect_in_place() {
+        let threads = current_num_threads();
+        for size in [0, 1, 2, threads - 1, threads, 2 * threads + 1, 100_000] {
+            for policy in policies() {
+                let odds: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .filter(|i| i % 2 == 1)
+                    .with_policy(policy)
+                    .collect();
+                assert!(odds.into_iter().eq((0..size).filter(|i| i % 2 == 1)));
+                // flat maps produce more elements than their length
+                let ranges: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .flat_map(|i| i..(i + i % 3))
+                    .with_policy(policy)
+                    .collect();
+                assert!(ranges
+                    .into_iter()
+                    .eq((0..size).flat_map(|i| i..(i + i % 3))));
+                // lines produce less elements than their length
+                // and might not be divided where asked
+                let text: String = (0..size).map(|i| format!("{}\n", i)).collect();
+                let lines: Vec<&str> = text.adapt_lines().with_policy(policy).collect();
+                assert_eq!(lines, text.lines().collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn other_collections_match_std() {
+        for size in [0, 1, 2, 1000, 10_000] {
+            for policy in policies() {
+                // duplicated keys: later values win
+                let map: HashMap<usize, usize> = (0..size)
+                    .into_adapt_iter()
+                    .map(|i| (i % 100, i))
+                    .with_policy(policy)
+                    .collect();
+                assert_eq!(map, (0..size).map(|i| (i % 100, i)).collect());
+                let set: HashSet<usize> = (0..size)
+                    .into_adapt_iter()
+                    .filter(|i| i % 3 == 0)
+                    .map(|i| i % 100)
+                    .with_policy(policy)
+                    .collect();
+                assert_eq!(
+                    set,
+                    (0..size).filter(|i| i % 3 == 0).map(|i| i % 100).collect()
+                );
+                let tree: BTreeMap<usize, usize> = (0..size)
+                    .into_adapt_iter()
+                    .filter(|i| i % 3 == 0)
+                    .map(|i| (i % 100, i))
+                    .with_policy(policy)
+                    .collect();
+                assert_eq!(
+                    tree,
+                    (0..size)
+                        .filter(|i| i % 3 == 0)
+                        .map(|i| (i % 100, i))
+                        .collect()
+                );
+                let tree_set: BTreeSet<usize> = (0..size)
+                    .into_adapt_iter()
+                    .map(|i| i % 100)
+                    .with_policy(policy)
+                    .collect();
+                assert_eq!(tree_set, (0..size).map(|i| i % 100).collect());
+                let list: LinkedList<usize> = (0..size)
+                    .into_adapt_iter()
+                    .filter(|i| i % 3 == 0)
+                    .with_policy(policy)
+                    .collect();
+                assert!(list.into_iter().eq((0..size).filter(|i| i % 3 == 0)));
+                let text: String = (0..size).map(|i| format!("é{}🦀\n", i)).collect();
+                let chars: String = text.adapt_chars().with_policy(policy).collect();
+                assert_eq!(chars, text);
+                let lines: String = text.adapt_lines().with_policy(policy).collect();
+                assert_eq!(lines, text.lines().collect::<String>());
+            }
+        }
+    }
+
+    #[test]
+    fn extending_keeps_existing_elements() {
+        for size in [0, 1, 2, 1000, 10_000] {
+            for policy in policies() {
+                // with and without spare capacity before extending
+                for capacity in [0, 2 * size + 10] {
+                    let mut expected: Vec<String> = Vec::with_capacity(capacity);
+                    expected.extend((0..7).map(|i| i.to_string()));
+                    let mut v = expected.clone();
+                    v.reserve(capacity);
+                    v.adapt_extend(
+                        (0..size)
+                            .into_adapt_iter()
+                            .map(|i| i.to_string())
+                            .with_policy(policy),
+                    );
+                    expected.extend((0..size).map(|i| i.to_string()));
+                    assert_eq!(v, expected);
+                    v.adapt_extend(
+                        (0..size)
+                            .into_adapt_iter()
+                            .filter(|i| i % 3 == 0)
+                            .map(|i| i.to_string())
+                            .with_policy(policy),
+                    );
+                    expected.extend((0..size).filter(|i| i % 3 == 0).map(|i| i.to_string()));
+                    assert_eq!(v, expected);
+                    // collecting replaces the content
+                    (0..size)
+                        .into_adapt_iter()
+                        .map(|i| i.to_string())
+                        .with_policy(policy)
+                        .collect_into_vec(&mut v);
+                    assert!(v.iter().cloned().eq((0..size).map(|i| i.to_string())));
+                    (0..size)
+                        .into_adapt_iter()
+                        .filter(|i| i % 3 == 0)
+                        .map(|i| i.to_string())
+                        .with_policy(policy)
+                        .collect_into_vec(&mut v);
+                    assert!(v
+                        .into_iter()
+                        .eq((0..size).filter(|i| i % 3 == 0).map(|i| i.to_string())));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn groups_keep_elements_in_order() {
+        for size in [0, 1, 2, 1000, 100_000] {
+            for policy in policies() {
+                let groups: Vec<Vec<usize>> = FromAdaptiveIterator::from_adapt_iter(
+                    (0..size).into_adapt_iter().with_policy(policy),
+                );
+                assert!(groups.iter().all(|group| !group.is_empty()));
+                assert!(groups.into_iter().flatten().eq(0..size));
+                let groups: Vec<Vec<usize>> = FromAdaptiveIterator::from_adapt_iter(
+                    (0..size)
+                        .into_adapt_iter()
+                        .filter(|i| i % 3 == 0)
+                        .with_policy(policy)
+                        .by_blocks(vec![1, 10, 100].into_iter()),
+                );
+                assert!(groups.iter().all(|group| !group.is_empty()));
+                assert!(groups
+                    .into_iter()
+                    .flatten()
+                    .eq((0..size).filter(|i| i % 3 == 0)));
+            }
+        }
+        // sequential runs give a single group
+        let groups: Vec<Vec<usize>> = FromAdaptiveIterator::from_adapt_iter(
+            (0..1000).into_adapt_iter().with_policy(Policy::Sequential),
+        );
+        assert_eq!(groups.len(), 1);
     }
 }
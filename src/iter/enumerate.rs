@@ -0,0 +1,53 @@
+use crate::prelude::*;
+use crate::traits::IndexedPower;
+use std::iter;
+use std::ops::RangeFrom;
+
+/// Iterator adaptor yielding `(index, item)` pairs.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Enumerate<I: AdaptiveIndexedIterator> {
+    pub(crate) base: I,
+    pub(crate) offset: usize,
+}
+
+impl<I: AdaptiveIndexedIterator> Divisible for Enumerate<I> {
+    type Power = IndexedPower;
+    fn base_length(&self) -> usize {
+        self.base.base_length()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<I: AdaptiveIndexedIterator> DivisibleIntoBlocks for Enumerate<I> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.base.divide_at(index);
+        let right_offset = self.offset + left.base_length();
+        (
+            Enumerate {
+                base: left,
+                offset: self.offset,
+            },
+            Enumerate {
+                base: right,
+                offset: right_offset,
+            },
+        )
+    }
+}
+
+impl<I: AdaptiveIndexedIterator> IntoIterator for Enumerate<I> {
+    type Item = (usize, I::Item);
+    type IntoIter = iter::Zip<RangeFrom<usize>, I::IntoIter>;
+    fn into_iter(self) -> Self::IntoIter {
+        (self.offset..).zip(self.base)
+    }
+}
+
+impl<I: AdaptiveIndexedIterator> AdaptiveIterator for Enumerate<I> {}
+impl<I: AdaptiveIndexedIterator> AdaptiveIndexedIterator for Enumerate<I> {}
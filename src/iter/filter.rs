@@ -1,6 +1,6 @@
-use super::{AdaptiveIterator, Divisible, DivisibleIntoBlocks};
+use super::AdaptiveIterator;
 use crate::traits::BlockedPower;
-use derive_divisible::{Divisible, DivisibleIntoBlocks};
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
 use std::iter;
 
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
@@ -22,7 +22,7 @@ impl<I: AdaptiveIterator, P: Fn(&I::Item) -> bool + Clone + Send + Sync> IntoIte
     }
 }
 
-impl<I: AdaptiveIterator, P: Fn(&I::Item) -> bool + Send + Sync + Copy> AdaptiveIterator
+impl<I: AdaptiveIterator, P: Fn(&I::Item) -> bool + Send + Sync + Clone> AdaptiveIterator
     for Filter<I, P>
 {
 }
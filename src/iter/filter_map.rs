@@ -0,0 +1,28 @@
+use super::AdaptiveIterator;
+use crate::traits::BlockedPower;
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
+use std::iter;
+
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(BlockedPower)]
+pub struct FilterMap<I: AdaptiveIterator, F: Clone + Send + Sync> {
+    pub(crate) iter: I,
+    #[divide_by(clone)]
+    pub(crate) filter_op: F,
+}
+
+impl<R, I: AdaptiveIterator, F: Fn(I::Item) -> Option<R> + Clone + Send + Sync> IntoIterator
+    for FilterMap<I, F>
+{
+    type Item = R;
+    type IntoIter = iter::FilterMap<I::IntoIter, F>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter.into_iter().filter_map(self.filter_op)
+    }
+}
+
+impl<R, I: AdaptiveIterator, F: Fn(I::Item) -> Option<R> + Clone + Send + Sync> AdaptiveIterator
+    for FilterMap<I, F>
+{
+}
@@ -0,0 +1,132 @@
+use crate::prelude::*;
+use crate::traits::BlockedPower;
+use std::iter;
+use std::option;
+
+/// Iterator adaptor mapping each element to an inner iterable and flattening the results.
+/// Inner iterables are `DivisibleIntoBlocks` and can also be divided by the scheduler:
+/// when only one outer element remains we open it and divide its content.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct FlatMap<I: AdaptiveIterator, F: Clone + Send + Sync, U> {
+    // inner iterable we are currently consuming (if any)
+    pub(crate) front: Option<U>,
+    // remaining outer elements
+    pub(crate) base: I,
+    pub(crate) map_op: F,
+}
+
+/// Iterator adaptor flattening nested iterables.
+pub type Flatten<I> = FlatMap<
+    I,
+    fn(<I as IntoIterator>::Item) -> <I as IntoIterator>::Item,
+    <I as IntoIterator>::Item,
+>;
+
+impl<I, F, U> FlatMap<I, F, U>
+where
+    I: AdaptiveIterator,
+    F: Fn(I::Item) -> U + Clone + Send + Sync,
+    U: IntoIterator + DivisibleIntoBlocks,
+{
+    fn front_length(&self) -> usize {
+        self.front.as_ref().map(|f| f.base_length()).unwrap_or(0)
+    }
+    /// If only one outer element remains, open it so that its content can be divided.
+    pub(crate) fn open(self) -> Self {
+        if self.front_length() == 0 && self.base.base_length() == 1 {
+            let (last, empty) = self.base.divide_at(1);
+            let front = last.into_iter().next().map(&self.map_op);
+            FlatMap {
+                front,
+                base: empty,
+                map_op: self.map_op,
+            }
+        } else {
+            self
+        }
+    }
+}
+
+impl<I, F, U> Divisible for FlatMap<I, F, U>
+where
+    I: AdaptiveIterator,
+    F: Fn(I::Item) -> U + Clone + Send + Sync,
+    U: IntoIterator + DivisibleIntoBlocks,
+{
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.front_length() + self.base.base_length()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+}
+
+impl<I, F, U> DivisibleIntoBlocks for FlatMap<I, F, U>
+where
+    I: AdaptiveIterator,
+    F: Fn(I::Item) -> U + Clone + Send + Sync,
+    U: IntoIterator + DivisibleIntoBlocks,
+{
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let front_length = self.front_length();
+        if index < front_length {
+            let (left_front, right_front) = self.front.unwrap().divide_at(index);
+            let (empty, base) = self.base.divide_at(0);
+            (
+                FlatMap {
+                    front: Some(left_front),
+                    base: empty,
+                    map_op: self.map_op.clone(),
+                },
+                FlatMap {
+                    front: Some(right_front),
+                    base,
+                    map_op: self.map_op,
+                },
+            )
+        } else {
+            let (left_base, right_base) = self.base.divide_at(index - front_length);
+            (
+                FlatMap {
+                    front: self.front,
+                    base: left_base,
+                    map_op: self.map_op.clone(),
+                }
+                .open(),
+                FlatMap {
+                    front: None,
+                    base: right_base,
+                    map_op: self.map_op,
+                }
+                .open(),
+            )
+        }
+    }
+}
+
+impl<I, F, U> IntoIterator for FlatMap<I, F, U>
+where
+    I: AdaptiveIterator,
+    F: Fn(I::Item) -> U + Clone + Send + Sync,
+    U: IntoIterator + DivisibleIntoBlocks,
+{
+    type Item = U::Item;
+    type IntoIter =
+        iter::Chain<iter::Flatten<option::IntoIter<U>>, iter::FlatMap<I::IntoIter, U, F>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.front
+            .into_iter()
+            .flatten()
+            .chain(self.base.into_iter().flat_map(self.map_op))
+    }
+}
+
+impl<I, F, U> AdaptiveIterator for FlatMap<I, F, U>
+where
+    I: AdaptiveIterator,
+    F: Fn(I::Item) -> U + Clone + Send + Sync,
+    U: IntoIterator + DivisibleIntoBlocks,
+{
+}
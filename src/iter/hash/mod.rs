@@ -1,46 +1,305 @@
 //! adaptive iterators on hashmaps
+//!
+//! We work on `hashbrown`'s tables: their raw api gives us access to the buckets
+//! by index, which we can divide like any range.
+//!
+//! std's `HashMap` and `HashSet` do not give access to their buckets so all functions
+//! here take `hashbrown`'s types (`par_keys`, `par_iter` and `par_elements` used to take
+//! std's ones). Std collections convert with `.into_iter().collect()`.
 
 use crate::prelude::*;
-use std::collections::{HashMap, HashSet};
+use crate::traits::BlockedPower;
+use hashbrown::raw::RawTable;
+use hashbrown::{HashMap, HashSet};
 use std::hash::BuildHasher;
 use std::hash::Hash;
-mod toxic; //don't open this
-use self::toxic::{extract_hashmap_slices, extract_hashset_slices};
+use std::marker::PhantomData;
+use std::ops::Range;
 
-pub trait AdaptiveHashMap<'a> {
-    type Iterator;
-    fn adapt_keys(&'a self) -> Self::Iterator;
+/// A range of buckets of a hash table.
+/// Iterating on it yields references to the elements stored in the full buckets.
+/// Lengths count buckets, not elements, so we only have a `BlockedPower`.
+pub struct Buckets<'a, T> {
+    table: &'a RawTable<T>,
+    range: Range<usize>,
 }
 
-pub fn par_keys<'a, K: Send + Sync + Eq + Hash, V: Send + Sync, S: BuildHasher>(
-    hashmap: &'a HashMap<K, V, S>,
-) -> impl AdaptiveIterator<Item = &'a K> {
-    let (hashes, pairs) = unsafe { extract_hashmap_slices(hashmap) };
-    hashes
+impl<'a, T> Buckets<'a, T> {
+    /// All buckets of given table.
+    pub fn new(table: &'a RawTable<T>) -> Self {
+        Buckets {
+            table,
+            range: 0..table.buckets(),
+        }
+    }
+}
+
+impl<'a, T: Sync> Divisible for Buckets<'a, T> {
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.range.len()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<'a, T: Sync> DivisibleIntoBlocks for Buckets<'a, T> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.range.divide_at(index);
+        (
+            Buckets {
+                table: self.table,
+                range: left,
+            },
+            Buckets {
+                table: self.table,
+                range: right,
+            },
+        )
+    }
+}
+
+impl<'a, T: Sync> IntoIterator for Buckets<'a, T> {
+    type Item = &'a T;
+    type IntoIter = BucketsIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        BucketsIter {
+            table: self.table,
+            range: self.range,
+        }
+    }
+}
+
+/// Sequential iterator on the full buckets of a `Buckets` range.
+pub struct BucketsIter<'a, T> {
+    table: &'a RawTable<T>,
+    range: Range<usize>,
+}
+
+impl<'a, T> Iterator for BucketsIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = self.table;
+        // ranges never exceed the number of buckets and we only open full buckets.
+        self.range
+            .find(|&index| unsafe { table.is_bucket_full(index) })
+            .map(|index| unsafe { table.bucket(index).as_ref() })
+    }
+}
+
+/// A range of buckets of a hash table we borrow mutably.
+/// Iterating on it yields mutable references to the elements stored in the full buckets.
+pub struct BucketsMut<'a, T> {
+    // we only use the table to read the control bytes and compute buckets addresses.
+    // since ranges are disjoint no element is ever reached twice.
+    table: &'a RawTable<T>,
+    range: Range<usize>,
+    marker: PhantomData<&'a mut T>,
+}
+
+// like `&mut [T]`
+unsafe impl<'a, T: Send> Send for BucketsMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for BucketsMut<'a, T> {}
+
+impl<'a, T> BucketsMut<'a, T> {
+    /// All buckets of given table.
+    pub fn new(table: &'a mut RawTable<T>) -> Self {
+        let range = 0..table.buckets();
+        BucketsMut {
+            table,
+            range,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Send + Sync> Divisible for BucketsMut<'a, T> {
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.range.len()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<'a, T: Send + Sync> DivisibleIntoBlocks for BucketsMut<'a, T> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.range.divide_at(index);
+        (
+            BucketsMut {
+                table: self.table,
+                range: left,
+                marker: PhantomData,
+            },
+            BucketsMut {
+                table: self.table,
+                range: right,
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a, T: Send + Sync> IntoIterator for BucketsMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = BucketsIterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        BucketsIterMut {
+            table: self.table,
+            range: self.range,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Sequential iterator on the full buckets of a `BucketsMut` range.
+pub struct BucketsIterMut<'a, T> {
+    table: &'a RawTable<T>,
+    range: Range<usize>,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for BucketsIterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = self.table;
+        // same as `BucketsIter` but each index is only visited once.
+        self.range
+            .find(|&index| unsafe { table.is_bucket_full(index) })
+            .map(|index| unsafe { table.bucket(index).as_mut() })
+    }
+}
+
+/// Adaptive iterator on the keys of a hashmap.
+///
+/// # Example
+///
+/// ```
+/// use hashbrown::HashMap;
+/// use rayon_adaptive::par_keys;
+/// use rayon_adaptive::prelude::*;
+///
+/// let h: HashMap<u32, u32> = (0..1000).map(|i| (i, i + 1)).collect();
+/// let s: u32 = par_keys(&h).sum();
+/// assert_eq!(s, 500 * 999);
+/// ```
+pub fn par_keys<K: Send + Sync + Eq + Hash, V: Send + Sync, S: BuildHasher>(
+    hashmap: &HashMap<K, V, S>,
+) -> impl AdaptiveIterator<Item = &K, Power = BlockedPower> {
+    Buckets::new(hashmap.raw_table())
         .into_adapt_iter()
-        .zip(pairs.into_adapt_iter())
-        .filter(|&(&h, _)| h != 0)
-        .map(|(_, &(ref k, _))| k)
+        .map(|(k, _)| k)
 }
 
-pub fn par_iter<'a, K: Send + Sync + Eq + Hash, V: Send + Sync, S: BuildHasher>(
-    hashmap: &'a HashMap<K, V, S>,
-) -> impl AdaptiveIterator<Item = (&'a K, &'a V)> {
-    let (hashes, pairs) = unsafe { extract_hashmap_slices(hashmap) };
-    hashes
+/// Adaptive iterator on the values of a hashmap.
+pub fn par_values<K: Send + Sync + Eq + Hash, V: Send + Sync, S: BuildHasher>(
+    hashmap: &HashMap<K, V, S>,
+) -> impl AdaptiveIterator<Item = &V, Power = BlockedPower> {
+    Buckets::new(hashmap.raw_table())
         .into_adapt_iter()
-        .zip(pairs.into_adapt_iter())
-        .filter(|&(&h, _)| h != 0)
-        .map(|(_, &(ref k, ref v))| (k, v))
+        .map(|(_, v)| v)
 }
 
-pub fn par_elements<'a, K: Send + Sync + Eq + Hash, S: BuildHasher>(
-    hashset: &'a HashSet<K, S>,
-) -> impl AdaptiveIterator<Item = &'a K> {
-    let (hashes, pairs) = unsafe { extract_hashset_slices(hashset) };
-    hashes
+/// Adaptive iterator on mutable references to the values of a hashmap.
+///
+/// # Example
+///
+/// ```
+/// use hashbrown::HashMap;
+/// use rayon_adaptive::{par_iter, par_values_mut};
+/// use rayon_adaptive::prelude::*;
+///
+/// let mut h: HashMap<u32, u32> = (0..1000).map(|i| (i, 0)).collect();
+/// par_values_mut(&mut h).for_each(|v| *v += 1);
+/// assert!(par_iter(&h).all(|(_, &v)| v == 1));
+/// ```
+pub fn par_values_mut<K: Send + Sync + Eq + Hash, V: Send + Sync, S: BuildHasher>(
+    hashmap: &mut HashMap<K, V, S>,
+) -> impl AdaptiveIterator<Item = &mut V, Power = BlockedPower> {
+    BucketsMut::new(hashmap.raw_table_mut())
         .into_adapt_iter()
-        .zip(pairs.into_adapt_iter())
-        .filter(|&(&h, _)| h != 0)
-        .map(|(_, &(ref k, _))| k)
+        .map(|(_, v)| v)
+}
+
+/// Adaptive iterator on the (key, value) pairs of a hashmap.
+pub fn par_iter<K: Send + Sync + Eq + Hash, V: Send + Sync, S: BuildHasher>(
+    hashmap: &HashMap<K, V, S>,
+) -> impl AdaptiveIterator<Item = (&K, &V), Power = BlockedPower> {
+    Buckets::new(hashmap.raw_table())
+        .into_adapt_iter()
+        .map(|(k, v)| (k, v))
+}
+
+/// Adaptive iterator on the elements of a hashset.
+///
+/// # Example
+///
+/// ```
+/// use hashbrown::HashSet;
+/// use rayon_adaptive::par_elements;
+/// use rayon_adaptive::prelude::*;
+///
+/// let s: HashSet<u32> = (0..1000).collect();
+/// assert_eq!(par_elements(&s).filter(|&&e| e % 2 == 0).count(), 500);
+/// ```
+pub fn par_elements<K: Send + Sync + Eq + Hash, S: BuildHasher>(
+    hashset: &HashSet<K, S>,
+) -> impl AdaptiveIterator<Item = &K, Power = BlockedPower> {
+    Buckets::new(hashset.raw_table())
+        .into_adapt_iter()
+        .map(|(k, _)| k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Policy;
+
+    #[test]
+    fn all_elements_are_visited_exactly_once() {
+        for &size in &[0, 1, 10, 1000, 100_000] {
+            let mut h: HashMap<usize, usize> = (0..size).map(|i| (i, 2 * i)).collect();
+            let s: HashSet<usize> = (0..size).collect();
+            for &policy in &[
+                Policy::Sequential,
+                Policy::Join(10),
+                Policy::JoinContext(1),
+                Policy::DepJoin(100),
+                Policy::Adaptive(1, 100),
+                Policy::DefaultPolicy,
+            ] {
+                let mut keys: Vec<usize> = par_keys(&h).cloned().with_policy(policy).collect();
+                keys.sort();
+                assert!(keys.into_iter().eq(0..size));
+                let mut values: Vec<usize> = par_values(&h).cloned().with_policy(policy).collect();
+                values.sort();
+                assert!(values.into_iter().eq((0..size).map(|i| 2 * i)));
+                let mut pairs: Vec<(usize, usize)> = par_iter(&h)
+                    .map(|(&k, &v)| (k, v))
+                    .with_policy(policy)
+                    .collect();
+                pairs.sort();
+                assert!(pairs.into_iter().eq((0..size).map(|i| (i, 2 * i))));
+                let mut elements: Vec<usize> =
+                    par_elements(&s).cloned().with_policy(policy).collect();
+                elements.sort();
+                assert!(elements.into_iter().eq(0..size));
+                par_values_mut(&mut h)
+                    .with_policy(policy)
+                    .for_each(|v| *v += 1);
+                assert!(h.iter().all(|(&k, &v)| v == 2 * k + 1));
+                h.values_mut().for_each(|v| *v -= 1);
+            }
+        }
+    }
 }
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
+use std::iter;
+
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(I::Power)]
+pub struct Inspect<I: AdaptiveIterator, F: Clone + Send + Sync> {
+    pub(crate) base: I,
+    #[divide_by(clone)]
+    pub(crate) inspect_op: F,
+}
+
+impl<I: AdaptiveIterator, F: Fn(&I::Item) + Clone + Send + Sync> IntoIterator for Inspect<I, F> {
+    type Item = I::Item;
+    type IntoIter = iter::Inspect<I::IntoIter, F>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.base.into_iter().inspect(self.inspect_op)
+    }
+}
+
+impl<I: AdaptiveIterator, F: Fn(&I::Item) + Clone + Send + Sync> AdaptiveIterator
+    for Inspect<I, F>
+{
+}
+impl<I: AdaptiveIndexedIterator, F: Fn(&I::Item) + Clone + Send + Sync> AdaptiveIndexedIterator
+    for Inspect<I, F>
+{
+}
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use derive_divisible::{Divisible, DivisibleIntoBlocks};
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 #[derive(Divisible, DivisibleIntoBlocks)]
 #[power(I::Power)]
@@ -15,7 +15,5 @@ impl<I: IntoIterator + DivisibleIntoBlocks> IntoIterator for Iter<I> {
     }
 }
 
-impl<I: IntoIterator + DivisibleAtIndex> DivisibleAtIndex for Iter<I> {}
-
 impl<I: IntoIterator + DivisibleIntoBlocks> AdaptiveIterator for Iter<I> {}
 impl<I: IntoIterator + DivisibleAtIndex> AdaptiveIndexedIterator for Iter<I> {}
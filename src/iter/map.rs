@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use derive_divisible::{Divisible, DivisibleIntoBlocks};
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
 use std::iter;
 
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
@@ -21,13 +21,11 @@ impl<R: Send, I: AdaptiveIterator, F: Fn(I::Item) -> R + Clone + Send + Sync> In
     }
 }
 
-impl<I: AdaptiveIndexedIterator, F: Send + Sync + Clone> DivisibleAtIndex for Map<I, F> {}
-
-impl<R: Send, I: AdaptiveIterator, F: Fn(I::Item) -> R + Send + Sync + Copy> AdaptiveIterator
+impl<R: Send, I: AdaptiveIterator, F: Fn(I::Item) -> R + Send + Sync + Clone> AdaptiveIterator
     for Map<I, F>
 {
 }
-impl<R: Send, I: AdaptiveIndexedIterator, F: Fn(I::Item) -> R + Send + Sync + Copy>
+impl<R: Send, I: AdaptiveIndexedIterator, F: Fn(I::Item) -> R + Send + Sync + Clone>
     AdaptiveIndexedIterator for Map<I, F>
 {
 }
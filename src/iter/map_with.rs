@@ -0,0 +1,112 @@
+//! Maps with a mutable state local to each division.
+use crate::prelude::*;
+use rayon_adaptive_derive::{Divisible, DivisibleIntoBlocks};
+
+/// Map with a state cloned on each division.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(I::Power)]
+pub struct MapWith<I: AdaptiveIterator, T: Clone + Send + Sync, F: Clone + Send + Sync> {
+    pub(crate) base: I,
+    #[divide_by(clone)]
+    pub(crate) state: T,
+    #[divide_by(clone)]
+    pub(crate) map_op: F,
+}
+
+/// Map with a state initialized by `init` on each sequential iteration.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(I::Power)]
+pub struct MapInit<I: AdaptiveIterator, INIT: Clone + Send + Sync, F: Clone + Send + Sync> {
+    pub(crate) base: I,
+    #[divide_by(clone)]
+    pub(crate) init: INIT,
+    #[divide_by(clone)]
+    pub(crate) map_op: F,
+}
+
+/// Sequential iterator for `MapWith` and `MapInit`.
+pub struct MapStateIter<I, T, F> {
+    base: I,
+    state: T,
+    map_op: F,
+}
+
+impl<R, I: Iterator, T, F: Fn(&mut T, I::Item) -> R> Iterator for MapStateIter<I, T, F> {
+    type Item = R;
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = &mut self.state;
+        let map_op = &self.map_op;
+        self.base.next().map(|e| map_op(state, e))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+}
+
+impl<R, I, T, F> IntoIterator for MapWith<I, T, F>
+where
+    I: AdaptiveIterator,
+    T: Clone + Send + Sync,
+    F: Fn(&mut T, I::Item) -> R + Clone + Send + Sync,
+{
+    type Item = R;
+    type IntoIter = MapStateIter<I::IntoIter, T, F>;
+    fn into_iter(self) -> Self::IntoIter {
+        MapStateIter {
+            base: self.base.into_iter(),
+            state: self.state,
+            map_op: self.map_op,
+        }
+    }
+}
+
+impl<R, I, T, INIT, F> IntoIterator for MapInit<I, INIT, F>
+where
+    I: AdaptiveIterator,
+    INIT: Fn() -> T + Clone + Send + Sync,
+    F: Fn(&mut T, I::Item) -> R + Clone + Send + Sync,
+{
+    type Item = R;
+    type IntoIter = MapStateIter<I::IntoIter, T, F>;
+    fn into_iter(self) -> Self::IntoIter {
+        MapStateIter {
+            base: self.base.into_iter(),
+            state: (self.init)(),
+            map_op: self.map_op,
+        }
+    }
+}
+
+impl<R, I, T, F> AdaptiveIterator for MapWith<I, T, F>
+where
+    I: AdaptiveIterator,
+    T: Clone + Send + Sync,
+    F: Fn(&mut T, I::Item) -> R + Clone + Send + Sync,
+{
+}
+
+impl<R, I, T, F> AdaptiveIndexedIterator for MapWith<I, T, F>
+where
+    I: AdaptiveIndexedIterator,
+    T: Clone + Send + Sync,
+    F: Fn(&mut T, I::Item) -> R + Clone + Send + Sync,
+{
+}
+
+impl<R, I, T, INIT, F> AdaptiveIterator for MapInit<I, INIT, F>
+where
+    I: AdaptiveIterator,
+    INIT: Fn() -> T + Clone + Send + Sync,
+    F: Fn(&mut T, I::Item) -> R + Clone + Send + Sync,
+{
+}
+
+impl<R, I, T, INIT, F> AdaptiveIndexedIterator for MapInit<I, INIT, F>
+where
+    I: AdaptiveIndexedIterator,
+    INIT: Fn() -> T + Clone + Send + Sync,
+    F: Fn(&mut T, I::Item) -> R + Clone + Send + Sync,
+{
+}
@@ -1,5 +1,10 @@
 use crate::activated_input::ActivatedInput;
-use crate::folders::{fold::Fold, iterator_fold::AdaptiveIteratorFold};
+use crate::algorithms::merge_sort::collect_sorted_by_key;
+use crate::algorithms::prefix::collect_prefix;
+use crate::folders::{
+    fold::Fold,
+    iterator_fold::{AdaptiveIteratorFold, AdaptiveIteratorFoldWith},
+};
 use crate::prelude::*;
 use crate::traits::{BlockedOrMore, BlockedPower};
 use std::iter::Empty;
@@ -10,17 +15,38 @@ pub mod iter;
 use self::iter::Iter;
 pub mod zip;
 use self::zip::Zip;
+pub mod chain;
+use self::chain::Chain;
+pub mod enumerate;
+use self::enumerate::Enumerate;
+pub mod flat_map;
+use self::flat_map::{FlatMap, Flatten};
+pub mod rev;
+use self::rev::Rev;
+pub mod step_by;
+use self::step_by::StepBy;
 mod cloned;
 use self::cloned::Cloned;
 mod filter;
 use self::filter::Filter;
+pub mod filter_map;
+use self::filter_map::FilterMap;
+pub mod inspect;
+use self::inspect::Inspect;
+pub mod map_with;
+use self::map_with::{MapInit, MapWith};
+pub mod bridge;
+use self::bridge::ParIter;
+pub mod buffered;
 use crate::policy::ParametrizedInput;
 use std;
 use std::cmp::min;
 mod collect;
-pub use self::collect::{FromAdaptiveBlockedIterator, FromAdaptiveIndexedIterator};
-// pub(crate) mod hash;
-pub(crate) mod str;
+pub use self::collect::{
+    AdaptiveExtend, FromAdaptiveBlockedIterator, FromAdaptiveIndexedIterator, FromAdaptiveIterator,
+};
+pub(crate) mod hash;
+pub mod str;
 use crate::utils::powers;
 use crate::utils::AbortingDivisible;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -42,8 +68,6 @@ pub trait AdaptiveIterator: IntoIterator + DivisibleIntoBlocks {
     {
         Cloned { it: self }
     }
-    //TODO: functions implement Copy but not clone ?????
-    //what about Sync and Send ???
     fn filter<P: Fn(&Self::Item) -> bool + Clone + Sync + Send>(
         self,
         predicate: P,
@@ -53,9 +77,206 @@ pub trait AdaptiveIterator: IntoIterator + DivisibleIntoBlocks {
             predicate,
         }
     }
-    fn map<R: Send, F: Fn(Self::Item) -> R + Send + Sync + Copy>(self, map_op: F) -> Map<Self, F> {
+    /// Filter and map at the same time, keeping only `Some` results.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<usize> = (0..1000)
+    ///     .into_adapt_iter()
+    ///     .filter_map(|i| if i % 3 == 0 { Some(i / 3) } else { None })
+    ///     .collect();
+    /// assert_eq!(v, (0..334).collect::<Vec<usize>>());
+    /// ```
+    fn filter_map<R: Send, F: Fn(Self::Item) -> Option<R> + Clone + Sync + Send>(
+        self,
+        filter_op: F,
+    ) -> FilterMap<Self, F> {
+        FilterMap {
+            iter: self,
+            filter_op,
+        }
+    }
+    fn map<R: Send, F: Fn(Self::Item) -> R + Send + Sync + Clone>(self, map_op: F) -> Map<Self, F> {
         Map { base: self, map_op }
     }
+    /// Call `inspect_op` on each element before passing it on.
+    fn inspect<F: Fn(&Self::Item) + Clone + Sync + Send>(self, inspect_op: F) -> Inspect<Self, F> {
+        Inspect {
+            base: self,
+            inspect_op,
+        }
+    }
+    /// Map with a mutable state.
+    /// Each division gets its own clone of `state`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<usize> = (0..1000)
+    ///     .into_adapt_iter()
+    ///     .map_with(Vec::new(), |buffer: &mut Vec<usize>, i| {
+    ///         buffer.clear();
+    ///         buffer.extend(0..(i % 10));
+    ///         buffer.len()
+    ///     })
+    ///     .collect();
+    /// assert_eq!(v, (0..1000).map(|i| i % 10).collect::<Vec<usize>>());
+    /// ```
+    fn map_with<R, T, F>(self, state: T, map_op: F) -> MapWith<Self, T, F>
+    where
+        R: Send,
+        T: Clone + Send + Sync,
+        F: Fn(&mut T, Self::Item) -> R + Clone + Sync + Send,
+    {
+        MapWith {
+            base: self,
+            state,
+            map_op,
+        }
+    }
+    /// Map with a mutable state.
+    /// State is created with `init` each time we start iterating sequentially
+    /// on a part of the input.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let s: u64 = (0..1000)
+    ///     .into_adapt_iter()
+    ///     .map_init(|| 0u64, |calls: &mut u64, i| {
+    ///         *calls += 1;
+    ///         i as u64
+    ///     })
+    ///     .sum();
+    /// assert_eq!(s, 999 * 500);
+    /// ```
+    fn map_init<R, T, INIT, F>(self, init: INIT, map_op: F) -> MapInit<Self, INIT, F>
+    where
+        R: Send,
+        INIT: Fn() -> T + Clone + Sync + Send,
+        F: Fn(&mut T, Self::Item) -> R + Clone + Sync + Send,
+    {
+        MapInit {
+            base: self,
+            init,
+            map_op,
+        }
+    }
+    /// Map each element to an iterable and iterate on all their contents.
+    /// Inner iterables need to be `DivisibleIntoBlocks` since the scheduler is
+    /// also allowed to divide them.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let s: usize = (0..1000).into_adapt_iter().flat_map(|i| 0..i).sum();
+    /// assert_eq!(s, (0..1000).flat_map(|i| 0..i).sum());
+    /// // nested parallelism is also available on a single outer element
+    /// let s: usize = (0..1).into_adapt_iter().flat_map(|_| 0..100_000).sum();
+    /// assert_eq!(s, 99_999 * 50_000);
+    /// ```
+    fn flat_map<U, F>(self, map_op: F) -> FlatMap<Self, F, U>
+    where
+        F: Fn(Self::Item) -> U + Clone + Send + Sync,
+        U: IntoIterator + DivisibleIntoBlocks,
+    {
+        FlatMap {
+            front: None,
+            base: self,
+            map_op,
+        }
+        .open()
+    }
+    /// Iterate on contents of all iterables we contain.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<usize> = (0..10)
+    ///     .into_adapt_iter()
+    ///     .map(|i| (10 * i)..(10 * (i + 1)))
+    ///     .flatten()
+    ///     .collect();
+    /// assert_eq!(v, (0..100).collect::<Vec<usize>>());
+    /// ```
+    fn flatten(self) -> Flatten<Self>
+    where
+        Self::Item: IntoIterator + DivisibleIntoBlocks,
+    {
+        fn identity<T>(x: T) -> T {
+            x
+        }
+        FlatMap {
+            front: None,
+            base: self,
+            map_op: identity as fn(Self::Item) -> Self::Item,
+        }
+        .open()
+    }
+    /// Iterate in reverse order.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<_> = (0..1000).into_adapt_iter().rev().collect();
+    /// assert_eq!(v, (0..1000).rev().collect::<Vec<_>>());
+    /// ```
+    fn rev(self) -> Rev<Self>
+    where
+        Self::IntoIter: DoubleEndedIterator,
+    {
+        Rev { base: self }
+    }
+    /// Iterate on self and then on other.
+    /// Both iterators need to have the same `Power`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<u32> = (0..500).collect();
+    /// let s: u32 = v.into_adapt_iter().chain(v.into_adapt_iter()).cloned().sum();
+    /// assert_eq!(s, 2 * 499 * 250);
+    /// ```
+    fn chain<B: AdaptiveIterator<Power = Self::Power, Item = Self::Item>>(
+        self,
+        other: B,
+    ) -> Chain<Self, B> {
+        Chain { a: self, b: other }
+    }
+    /// Turn into a rayon `ParallelIterator`.
+    /// Rayon divides us with `divide` (or `divide_at` for indexed iterators).
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<u32> = (0..1000).collect();
+    /// let s: u32 = v.into_adapt_iter().filter(|&&x| x % 2 == 0).into_par_iter().sum();
+    /// assert_eq!(s, 2 * 499 * 250);
+    /// // indexed iterators give indexed parallel iterators
+    /// let squares: Vec<usize> = (0..10)
+    ///     .into_adapt_iter()
+    ///     .map(|x| x * x)
+    ///     .into_par_iter()
+    ///     .rev()
+    ///     .collect();
+    /// assert_eq!(squares, vec![81, 64, 49, 36, 25, 16, 9, 4, 1, 0]);
+    /// ```
+    fn into_par_iter(self) -> ParIter<Self>
+    where
+        Self::Item: Send,
+    {
+        ParIter { base: self }
+    }
 }
 
 /// These iterators allow zipping, skipping and taking.
@@ -75,6 +296,53 @@ pub trait AdaptiveIndexedIterator: AdaptiveIterator + DivisibleAtIndex {
     fn zip<U: AdaptiveIndexedIterator>(self, other: U) -> Zip<Self, U> {
         Zip { a: self, b: other }
     }
+    /// Pair each element with its index.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v = vec![3u32; 1000];
+    /// assert!(v.into_adapt_iter().enumerate().skip(10).all(|(i, &e)| i >= 10 && e == 3));
+    /// ```
+    fn enumerate(self) -> Enumerate<Self> {
+        Enumerate {
+            base: self,
+            offset: 0,
+        }
+    }
+    /// Skip the first `n` elements.
+    fn skip(self, n: usize) -> Self {
+        let n = min(n, self.base_length());
+        self.divide_at(n).1
+    }
+    /// Only keep the first `n` elements.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<_> = (0..1000).into_adapt_iter().skip(100).take(10).collect();
+    /// assert_eq!(v, (100..110).collect::<Vec<_>>());
+    /// ```
+    fn take(self, n: usize) -> Self {
+        let n = min(n, self.base_length());
+        self.divide_at(n).0
+    }
+    /// Only keep one element out of `step`, starting with the first one.
+    /// Panics if `step` is 0.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<_> = (0..1000).into_adapt_iter().step_by(3).collect();
+    /// assert_eq!(v, (0..1000).step_by(3).collect::<Vec<_>>());
+    /// ```
+    fn step_by(self, step: usize) -> StepBy<Self> {
+        assert!(step != 0, "step_by requires a non zero step");
+        StepBy { base: self, step }
+    }
 }
 
 pub trait AdaptiveIteratorRunner<I: AdaptiveIterator, S: Iterator<Item = usize>>:
@@ -129,7 +397,8 @@ pub trait AdaptiveIteratorRunner<I: AdaptiveIterator, S: Iterator<Item = usize>>
     {
         let (input, policy, sizes) = self.input_policy_sizes();
         let len = input.base_length();
-        let base_size = min((len as f64).log(2.0).ceil() as usize, len);
+        // blocks must not be empty or we would never progress on single elements
+        let base_size = min((len as f64).log(2.0).ceil() as usize, len).max(1);
         input
             .with_policy(policy)
             .by_blocks(sizes.chain(powers(base_size)))
@@ -188,7 +457,8 @@ pub trait AdaptiveIteratorRunner<I: AdaptiveIterator, S: Iterator<Item = usize>>
         let base_size = std::cmp::min(
             (input.base_length() as f64).log(2.0).ceil() as usize,
             input.base_length(),
-        );
+        )
+        .max(1);
         ActivatedInput {
             input,
             folder: Fold {
@@ -279,6 +549,234 @@ pub trait AdaptiveIteratorRunner<I: AdaptiveIterator, S: Iterator<Item = usize>>
         .reduce(|a, b| a + b)
     }
 
+    /// Computes the minimum of all the items in the iterator.
+    /// If several elements are equally minimum, the first one is returned.
+    ///
+    /// Example:
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// assert_eq!((0..1000).into_adapt_iter().map(|x| (x + 500) % 1000).min(), Some(0))
+    /// ```
+    fn min(self) -> Option<I::Item>
+    where
+        I::Item: Ord + Send + Sync,
+    {
+        self.min_by(Ord::cmp)
+    }
+
+    /// Computes the minimum of all the items in the iterator with respect to given
+    /// comparison function.
+    /// If several elements are equally minimum, the first one is returned.
+    fn min_by<F>(self, compare: F) -> Option<I::Item>
+    where
+        I::Item: Send + Sync,
+        F: Fn(&I::Item, &I::Item) -> std::cmp::Ordering + Sync,
+    {
+        let compare_ref = &compare;
+        best_by(
+            self,
+            |block| block.min_by(compare_ref),
+            |a, b| {
+                if compare(a, b) == std::cmp::Ordering::Greater {
+                    std::cmp::Ordering::Less
+                } else {
+                    std::cmp::Ordering::Greater
+                }
+            },
+        )
+    }
+
+    /// Computes the maximum of all the items in the iterator with respect to given
+    /// comparison function.
+    /// If several elements are equally maximum, the last one is returned.
+    ///
+    /// Example:
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<(usize, usize)> = (0..1000).map(|i| (i % 7, i)).collect();
+    /// let m = v.into_adapt_iter().max_by(|a, b| a.0.cmp(&b.0));
+    /// assert_eq!(m, Some(&(6, 993)));
+    /// ```
+    fn max_by<F>(self, compare: F) -> Option<I::Item>
+    where
+        I::Item: Send + Sync,
+        F: Fn(&I::Item, &I::Item) -> std::cmp::Ordering + Sync,
+    {
+        let compare_ref = &compare;
+        best_by(self, |block| block.max_by(compare_ref), compare_ref)
+    }
+
+    /// Return the element giving the minimum value for given key function.
+    /// If several elements are equally minimum, the first one is returned.
+    fn min_by_key<K, F>(self, key: F) -> Option<I::Item>
+    where
+        I::Item: Send + Sync,
+        K: Ord,
+        F: Fn(&I::Item) -> K + Sync,
+    {
+        self.min_by(|a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Return the element giving the maximum value for given key function.
+    /// If several elements are equally maximum, the last one is returned.
+    ///
+    /// Example:
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let m = (1..1000).into_adapt_iter().max_by_key(|&x| x % 100);
+    /// assert_eq!(m, Some(999));
+    /// ```
+    fn max_by_key<K, F>(self, key: F) -> Option<I::Item>
+    where
+        I::Item: Send + Sync,
+        K: Ord,
+        F: Fn(&I::Item) -> K + Sync,
+    {
+        self.max_by(|a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Multiply all elements.
+    ///
+    /// Example:
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let p: u64 = (1..21).into_adapt_iter().map(|x| x as u64).product();
+    /// assert_eq!(p, (1..21).product());
+    /// ```
+    fn product<P>(self) -> P
+    where
+        P: std::iter::Product<I::Item> + Send + Sync + std::ops::Mul<Output = P>,
+    {
+        let (input, policy, sizes) = self.input_policy_sizes();
+        ActivatedInput {
+            input,
+            folder: Fold {
+                identity_op: || None.into_iter().product(),
+                fold_op: |p: P, i: I, limit: usize| {
+                    let (todo, remaining) = i.divide_at(limit);
+                    let p2 = todo.into_iter().product();
+                    (p * p2, remaining)
+                },
+                phantom: PhantomData,
+            },
+            policy,
+            sizes,
+            power: PhantomData,
+        }
+        .reduce(|a, b| a * b)
+    }
+
+    /// Find last e in iterator such that predicate(e) is true.
+    /// This is the mirror of `find_first`: blocks are taken from the right end
+    /// (with `divide_at_from_end`) so it is also work efficient.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// assert_eq!((0..1000).into_adapt_iter().find_last(|&x| x % 7 == 0), Some(994));
+    /// ```
+    fn find_last<P>(self, predicate: P) -> Option<I::Item>
+    where
+        P: Fn(&I::Item) -> bool + Sync + Send,
+        I::Item: Sync + Send,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        let (input, policy, sizes) = self.input_policy_sizes();
+        input
+            .rev()
+            .with_policy(policy)
+            .by_blocks(sizes)
+            .find_first(predicate)
+    }
+
+    /// Split elements into two collections: the ones for which the predicate
+    /// is true and the others. Order is preserved.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let (even, odd): (Vec<_>, Vec<_>) = (0..1000).into_adapt_iter().partition(|&x| x % 2 == 0);
+    /// assert_eq!(even, (0..500).map(|x| 2 * x).collect::<Vec<_>>());
+    /// assert_eq!(odd, (0..500).map(|x| 2 * x + 1).collect::<Vec<_>>());
+    /// ```
+    fn partition<C, P>(self, predicate: P) -> (C, C)
+    where
+        C: Default + Extend<I::Item> + IntoIterator<Item = I::Item> + Send + Sync,
+        P: Fn(&I::Item) -> bool + Sync + Send,
+    {
+        let (input, policy, sizes) = self.input_policy_sizes();
+        ActivatedInput {
+            input,
+            folder: Fold {
+                identity_op: || (C::default(), C::default()),
+                fold_op: |(mut accepted, mut rejected): (C, C), i: I, limit: usize| {
+                    let (todo, remaining) = i.divide_at(limit);
+                    for e in todo {
+                        if predicate(&e) {
+                            accepted.extend(std::iter::once(e))
+                        } else {
+                            rejected.extend(std::iter::once(e))
+                        }
+                    }
+                    ((accepted, rejected), remaining)
+                },
+                phantom: PhantomData,
+            },
+            policy,
+            sizes,
+            power: PhantomData,
+        }
+        .reduce(|(mut a1, mut r1), (a2, r2)| {
+            a1.extend(a2);
+            r1.extend(r2);
+            (a1, r1)
+        })
+    }
+
+    /// Turn an iterator on pairs into a pair of collections.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let (v1, v2): (Vec<_>, Vec<_>) = (0..1000).into_adapt_iter().map(|x| (x, 2 * x)).unzip();
+    /// assert_eq!(v1, (0..1000).collect::<Vec<_>>());
+    /// assert_eq!(v2, (0..1000).map(|x| 2 * x).collect::<Vec<_>>());
+    /// ```
+    fn unzip<A, B, CA, CB>(self) -> (CA, CB)
+    where
+        I: AdaptiveIterator<Item = (A, B)>,
+        CA: Default + Extend<A> + IntoIterator<Item = A> + Send + Sync,
+        CB: Default + Extend<B> + IntoIterator<Item = B> + Send + Sync,
+    {
+        let (input, policy, sizes) = self.input_policy_sizes();
+        ActivatedInput {
+            input,
+            folder: Fold {
+                identity_op: || (CA::default(), CB::default()),
+                fold_op: |(mut a, mut b): (CA, CB), i: I, limit: usize| {
+                    let (todo, remaining) = i.divide_at(limit);
+                    for (ea, eb) in todo {
+                        a.extend(std::iter::once(ea));
+                        b.extend(std::iter::once(eb));
+                    }
+                    ((a, b), remaining)
+                },
+                phantom: PhantomData,
+            },
+            policy,
+            sizes,
+            power: PhantomData,
+        }
+        .reduce(|(mut a1, mut b1), (a2, b2)| {
+            a1.extend(a2);
+            b1.extend(b2);
+            (a1, b1)
+        })
+    }
+
     /// Apply *op* on each element.
     ///
     /// Example:
@@ -338,6 +836,158 @@ pub trait AdaptiveIteratorRunner<I: AdaptiveIterator, S: Iterator<Item = usize>>
             power: PhantomData,
         }
     }
+
+    /// Collect all elements into given vector, replacing its content.
+    /// The vector's capacity is reused.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let mut v = Vec::with_capacity(1000);
+    /// (0..1000).into_adapt_iter().map(|e| 2 * e).collect_into_vec(&mut v);
+    /// (0..1000).into_adapt_iter().filter(|&e| e % 2 == 0).collect_into_vec(&mut v);
+    /// assert_eq!(v, (0..500).map(|e| 2 * e).collect::<Vec<_>>());
+    /// assert!(v.capacity() >= 1000);
+    /// ```
+    fn collect_into_vec(self, vector: &mut Vec<I::Item>)
+    where
+        I::Item: Send,
+        Vec<I::Item>: AdaptiveExtend<I::Item, I::Power>,
+    {
+        vector.clear();
+        vector.adapt_extend(self)
+    }
+
+    /// Fold all elements starting from clones of `init`.
+    /// Unlike `fold` no identity closure is needed but `init` is
+    /// cloned each time a new fold starts.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let s: Vec<usize> = (0..100)
+    ///     .into_adapt_iter()
+    ///     .fold_with(Vec::new(), |mut v, e| {
+    ///         v.push(e);
+    ///         v
+    ///     })
+    ///     .reduce(|mut v1, v2| {
+    ///         v1.extend(v2);
+    ///         v1
+    ///     });
+    /// assert_eq!(s, (0..100).collect::<Vec<_>>());
+    /// ```
+    fn fold_with<IO, F>(
+        self,
+        init: IO,
+        fold_op: F,
+    ) -> ActivatedInput<AdaptiveIteratorFoldWith<I, IO, F>, S, BlockedOrMore>
+    where
+        IO: Send + Sync + Clone,
+        F: Fn(IO, I::Item) -> IO + Sync + Send,
+    {
+        let (input, policy, sizes) = self.input_policy_sizes();
+        ActivatedInput {
+            input,
+            folder: AdaptiveIteratorFoldWith {
+                init,
+                fold_op,
+                phantom: PhantomData,
+            },
+            policy,
+            sizes,
+            power: PhantomData,
+        }
+    }
+
+    /// Reduce all elements with given associative operation.
+    /// No identity is needed: `None` is returned on empty iterators.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// // intervals hull has no cheap identity
+    /// let hull = (0..1000)
+    ///     .into_adapt_iter()
+    ///     .map(|x| ((x * 7) % 1000, (x * 7) % 1000))
+    ///     .reduce_with(|(a1, b1), (a2, b2)| (a1.min(a2), b1.max(b2)));
+    /// assert_eq!(hull, Some((0, 999)));
+    /// let empty = (0..0).into_adapt_iter().reduce_with(|a, b| a + b);
+    /// assert_eq!(empty, None);
+    /// ```
+    fn reduce_with<OP>(self, op: OP) -> Option<I::Item>
+    where
+        I::Item: Send + Sync,
+        OP: Fn(I::Item, I::Item) -> I::Item + Sync + Send,
+    {
+        let combine = |a: Option<I::Item>, b: Option<I::Item>| match (a, b) {
+            (Some(a), Some(b)) => Some(op(a, b)),
+            (a, None) => a,
+            (None, b) => b,
+        };
+        let (input, policy, sizes) = self.input_policy_sizes();
+        ActivatedInput {
+            input,
+            folder: Fold {
+                identity_op: || None,
+                fold_op: |o: Option<I::Item>, i: I, limit: usize| {
+                    let (todo, remaining) = i.divide_at(limit);
+                    let mut elements = todo.into_iter();
+                    let reduced = o
+                        .or_else(|| elements.next())
+                        .map(|first| elements.fold(first, &op));
+                    (reduced, remaining)
+                },
+                phantom: PhantomData,
+            },
+            policy,
+            sizes,
+            power: PhantomData,
+        }
+        .reduce(combine)
+    }
+}
+
+/// Reduce all elements with a "best" selection.
+/// `block_best` computes the best element of a sequential block and
+/// `compare` returns `Greater` if its first argument is strictly better than the second one.
+fn best_by<I, R, S, B, C>(runner: R, block_best: B, compare: C) -> Option<I::Item>
+where
+    I: AdaptiveIterator,
+    R: AdaptiveRunner<I, S>,
+    S: Iterator<Item = usize>,
+    I::Item: Send + Sync,
+    B: Fn(I::IntoIter) -> Option<I::Item> + Sync,
+    C: Fn(&I::Item, &I::Item) -> std::cmp::Ordering + Sync,
+{
+    let best = |a: Option<I::Item>, b: Option<I::Item>| match (a, b) {
+        (Some(a), Some(b)) => Some(if compare(&a, &b) == std::cmp::Ordering::Greater {
+            a
+        } else {
+            b
+        }),
+        (a, None) => a,
+        (None, b) => b,
+    };
+    let (input, policy, sizes) = runner.input_policy_sizes();
+    ActivatedInput {
+        input,
+        folder: Fold {
+            identity_op: || None,
+            fold_op: |previous_best, i: I, limit: usize| {
+                let (todo, remaining) = i.divide_at(limit);
+                (best(previous_best, block_best(todo.into_iter())), remaining)
+            },
+            phantom: PhantomData,
+        },
+        policy,
+        sizes,
+        power: PhantomData,
+    }
+    .reduce(best)
 }
 
 /// Specializations of AdaptiveIteratorRunner.
@@ -345,7 +995,9 @@ pub trait AdaptiveIndexedIteratorRunner<I: AdaptiveIndexedIterator, S: Iterator<
     AdaptiveRunner<I, S>
 {
     /// Collect turn an `AdaptiveIterator` into a collection.
-    /// As of now it is only implemented for `Vec`.
+    /// `Vec` has a specialized algorithm, other collections
+    /// (hash maps and sets, b-trees, linked lists, strings)
+    /// go through `FromAdaptiveIterator`.
     /// Collecting comes with different algorithms for each Divisibility type
     /// (`Divisible`, `DivisibleIntoBlocks`, `DivisibleAtIndex`)
     /// This version is the `DivisibleAtIndex` version and will incur very little overhead.
@@ -355,7 +1007,15 @@ pub trait AdaptiveIndexedIteratorRunner<I: AdaptiveIndexedIterator, S: Iterator<
     /// use rayon_adaptive::prelude::*;
     /// let v:Vec<_> = (0..10_000).into_adapt_iter().map(|i| i+1).collect();
     /// let vseq:Vec<_> = (0..=10_000).skip(1).collect();
-    /// assert_eq!(v, vseq)
+    /// assert_eq!(v, vseq);
+    ///
+    /// use std::collections::{BTreeMap, HashMap};
+    /// let squares: HashMap<_, _> = (0..1000).into_adapt_iter().map(|i| (i, i * i)).collect();
+    /// assert_eq!(squares[&30], 900);
+    /// let last_digits: BTreeMap<_, _> = (0..1000).into_adapt_iter().map(|i| (i % 10, i)).collect();
+    /// assert_eq!(last_digits.values().copied().collect::<Vec<_>>(), (990..1000).collect::<Vec<_>>());
+    /// let s: String = (0..26).into_adapt_iter().map(|i| (b'a' + i as u8) as char).collect();
+    /// assert_eq!(s, "abcdefghijklmnopqrstuvwxyz");
     /// ```
     fn collect<C>(self) -> C
     where
@@ -364,6 +1024,151 @@ pub trait AdaptiveIndexedIteratorRunner<I: AdaptiveIndexedIterator, S: Iterator<
     {
         FromAdaptiveIndexedIterator::from_adapt_iter(self)
     }
+
+    /// Collect into a sorted vector.
+    /// Each block is sorted as soon as it is produced and sorted blocks get merged
+    /// in parallel (like in `adaptive_sort`) so there is no separate sorting pass.
+    /// For now we require Copy. Sort is stable.
+    ///
+    /// Example
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<_> = (0..10_000).into_adapt_iter().map(|i| (i * 7919) % 10_000).collect_sorted();
+    /// assert_eq!(v, (0..10_000).collect::<Vec<_>>());
+    /// ```
+    fn collect_sorted(self) -> Vec<I::Item>
+    where
+        I::Item: Ord + Copy + Send + Sync,
+    {
+        self.sorted_by_key(|e| *e)
+    }
+
+    /// Collect into a vector sorted by given key (see `collect_sorted`).
+    ///
+    /// Example
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<_> = (0..1000).into_adapt_iter().map(|i| (i % 10, i)).sorted_by_key(|e| e.0);
+    /// // sort is stable
+    /// assert!(v.into_iter().eq((0..10).flat_map(|d| (0..100).map(move |i| (d, 10 * i + d)))));
+    /// ```
+    fn sorted_by_key<K, F>(self, key: F) -> Vec<I::Item>
+    where
+        I::Item: Copy + Send + Sync,
+        K: Ord,
+        F: Fn(&I::Item) -> K + Sync,
+    {
+        collect_sorted_by_key(self, key)
+    }
+
+    /// Collect all prefixes into a vector: element `i` is the fold with `op`
+    /// of the first `i + 1` elements.
+    /// `op` needs to be associative.
+    /// Like `adaptive_prefix` but elements need not be materialized first.
+    /// As in `adaptive_prefix` new elements come first: prefix `i` is `op(e_i, prefix_{i-1})`.
+    ///
+    /// Example
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v = (1..10_001).into_adapt_iter().map(|e| 2 * e as u64).prefix(|a, b| a + b);
+    /// assert_eq!(v, (1..10_001u64).map(|e| e * (e + 1)).collect::<Vec<_>>());
+    /// let letters = vec!["a", "b", "c"];
+    /// let reversed = letters.into_adapt_iter().map(|l| l.to_string()).prefix(|e, p| e.clone() + p);
+    /// assert_eq!(reversed, vec!["a", "ba", "cba"]);
+    /// ```
+    fn prefix<O>(self, op: O) -> Vec<I::Item>
+    where
+        I::Item: Clone + Send + Sync,
+        O: Fn(&I::Item, &I::Item) -> I::Item + Sync,
+    {
+        collect_prefix(self, None, op)
+    }
+
+    /// Collect all exclusive prefixes into a vector: element `i` is the fold with `op`
+    /// of `init` and the first `i` elements.
+    /// `op` needs to be associative.
+    /// Prefix `0` is `init` and prefix `i` is `op(e_{i-1}, prefix_{i-1})` (see `prefix`).
+    ///
+    /// Example
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let words = vec!["the", "adaptive", "scan", "computes", "offsets"];
+    /// let offsets = words.into_adapt_iter().map(|w| w.len()).exclusive_prefix(0, |a, b| a + b);
+    /// assert_eq!(offsets, vec![0, 3, 11, 15, 23]);
+    /// ```
+    fn exclusive_prefix<O>(self, init: I::Item, op: O) -> Vec<I::Item>
+    where
+        I::Item: Clone + Send + Sync,
+        O: Fn(&I::Item, &I::Item) -> I::Item + Sync,
+    {
+        collect_prefix(self, Some(init), op)
+    }
+
+    /// Return the index of the first element such that predicate(e) is true.
+    /// Like `find_first` this is work efficient.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<u32> = (0..1000).map(|x| x % 300).collect();
+    /// assert_eq!(v.into_adapt_iter().position_first(|&&x| x == 299), Some(299));
+    /// ```
+    fn position_first<P>(self, predicate: P) -> Option<usize>
+    where
+        P: Fn(&I::Item) -> bool + Sync + Send,
+        I::Item: Sync + Send,
+    {
+        let (input, policy, sizes) = self.input_policy_sizes();
+        input
+            .enumerate()
+            .with_policy(policy)
+            .by_blocks(sizes)
+            .find_first(|(_, e)| predicate(e))
+            .map(|(i, _)| i)
+    }
+
+    /// Return the index of any element such that predicate(e) is true.
+    fn position_any<P>(self, predicate: P) -> Option<usize>
+    where
+        P: Fn(&I::Item) -> bool + Sync + Send,
+        I::Item: Sync + Send,
+    {
+        let (input, policy, sizes) = self.input_policy_sizes();
+        input
+            .enumerate()
+            .with_policy(policy)
+            .by_blocks(sizes)
+            .find_any(|(_, e)| predicate(e))
+            .map(|(i, _)| i)
+    }
+
+    /// Return the index of the last element such that predicate(e) is true.
+    /// Like `find_last` this is work efficient.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let v: Vec<u32> = (0..1000).map(|x| x % 300).collect();
+    /// assert_eq!(v.into_adapt_iter().rposition(|&&x| x == 0), Some(900));
+    /// ```
+    fn rposition<P>(self, predicate: P) -> Option<usize>
+    where
+        P: Fn(&I::Item) -> bool + Sync + Send,
+        I::Item: Sync + Send,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        let (input, policy, sizes) = self.input_policy_sizes();
+        let len = input.base_length();
+        input
+            .rev()
+            .enumerate()
+            .with_policy(policy)
+            .by_blocks(sizes)
+            .find_first(|(_, e)| predicate(e))
+            .map(|(i, _)| len - 1 - i)
+    }
 }
 pub trait AdaptiveBlockedIteratorRunner<
     I: AdaptiveIterator<Power = BlockedPower>,
@@ -371,18 +1176,26 @@ pub trait AdaptiveBlockedIteratorRunner<
 >: AdaptiveRunner<I, S>
 {
     /// Collect turn an `AdaptiveIterator` into a collection.
-    /// As of now it is only implemented for `Vec`.
+    /// `Vec` has a specialized algorithm, other collections
+    /// (hash maps and sets, b-trees, linked lists, strings)
+    /// go through `FromAdaptiveIterator`.
     /// Collecting comes with different algorithms for each Divisibility type
     /// (`Divisible`, `DivisibleIntoBlocks`, `DivisibleAtIndex`)
-    /// This version is the `DivisibleIntoBlocks` version and will incur very some overhead
-    /// moving data twice.
+    /// This version is the `DivisibleIntoBlocks` version: elements are written directly
+    /// into the final vector and only the parts given to other threads get moved back.
     ///
     /// Example
     /// ```
     /// use rayon_adaptive::prelude::*;
     /// let v:Vec<_> = (0..10_000).into_adapt_iter().filter(|&i| i%2 == 0).collect();
     /// let vseq:Vec<_> = (0..5_000).map(|i| i*2).collect();
-    /// assert_eq!(v, vseq)
+    /// assert_eq!(v, vseq);
+    ///
+    /// use std::collections::{HashSet, LinkedList};
+    /// let odd: HashSet<_> = (0..1000).into_adapt_iter().filter(|&i| i % 2 == 1).collect();
+    /// assert_eq!(odd.len(), 500);
+    /// let l: LinkedList<_> = (0..1000).into_adapt_iter().filter(|&i| i % 3 == 0).collect();
+    /// assert!(l.into_iter().eq((0..1000).step_by(3)));
     /// ```
 
     fn collect<C>(self) -> C
@@ -414,3 +1227,402 @@ impl<I: AdaptiveIterator<Power = BlockedPower>> AdaptiveBlockedIteratorRunner<I,
     for I
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::Policy;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn policies() -> Vec<Policy> {
+        vec![
+            Policy::Sequential,
+            Policy::Rayon,
+            Policy::Join(1),
+            Policy::JoinContext(10),
+            Policy::JoinRatio(3, 1, 4),
+            Policy::DepJoin(7),
+            Policy::Adaptive(1, 16),
+            Policy::DefaultPolicy,
+        ]
+    }
+
+    const SIZES: [usize; 6] = [0, 1, 2, 7, 1000, 10_000];
+
+    #[test]
+    fn indexed_adaptors_match_std() {
+        for &size in &SIZES {
+            for policy in policies() {
+                let v: Vec<(usize, usize)> = (0..size)
+                    .into_adapt_iter()
+                    .map(|e| 2 * e)
+                    .enumerate()
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).map(|e| 2 * e).enumerate()));
+                for &n in &[0, 3, size / 2, size, size + 5] {
+                    let v: Vec<usize> = (0..size)
+                        .into_adapt_iter()
+                        .skip(n)
+                        .with_policy(policy)
+                        .collect();
+                    assert!(v.into_iter().eq((0..size).skip(n)));
+                    let v: Vec<usize> = (0..size)
+                        .into_adapt_iter()
+                        .take(n)
+                        .with_policy(policy)
+                        .collect();
+                    assert!(v.into_iter().eq((0..size).take(n)));
+                }
+                // enumerate after skip counts from the new start
+                let v: Vec<(usize, usize)> = (0..size)
+                    .into_adapt_iter()
+                    .skip(3)
+                    .enumerate()
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).skip(3).enumerate()));
+                for &step in &[1, 2, 3, 10] {
+                    let v: Vec<usize> = (0..size)
+                        .into_adapt_iter()
+                        .step_by(step)
+                        .with_policy(policy)
+                        .collect();
+                    assert!(v.into_iter().eq((0..size).step_by(step)));
+                    let v: Vec<usize> = (0..size)
+                        .into_adapt_iter()
+                        .step_by(step)
+                        .rev()
+                        .with_policy(policy)
+                        .collect();
+                    assert!(v.into_iter().eq((0..size).step_by(step).rev()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flat_maps_match_std() {
+        // some inner ranges are empty
+        let inner = |i: usize| i..(i + i % 3);
+        for &size in &SIZES {
+            for policy in policies() {
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .flat_map(inner)
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).flat_map(inner)));
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .flat_map(inner)
+                    .rev()
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).flat_map(inner).rev()));
+                for &modulo in &[1, 5, 2 * size + 1] {
+                    let found = (0..size)
+                        .into_adapt_iter()
+                        .flat_map(inner)
+                        .with_policy(policy)
+                        .find_last(|x| x % modulo == 0);
+                    assert_eq!(found, (0..size).flat_map(inner).rfind(|x| x % modulo == 0));
+                }
+                // only empty inner ranges
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .flat_map(|i| i..i)
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.is_empty());
+                let found = (0..size)
+                    .into_adapt_iter()
+                    .flat_map(|i| i..i)
+                    .with_policy(policy)
+                    .find_last(|_| true);
+                assert_eq!(found, None);
+                // a single outer element gets opened and divided
+                let v: Vec<usize> = (0..1)
+                    .into_adapt_iter()
+                    .flat_map(|_| 0..size)
+                    .rev()
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).rev()));
+            }
+        }
+    }
+
+    #[test]
+    fn filter_map_and_inspect_match_std() {
+        let half = |e: usize| if e % 2 == 1 { None } else { Some(e / 2) };
+        for &size in &SIZES {
+            for policy in policies() {
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .filter_map(half)
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).filter_map(half)));
+                let seen = AtomicUsize::new(0);
+                let s: usize = (0..size)
+                    .into_adapt_iter()
+                    .inspect(|&e| {
+                        seen.fetch_add(e, Ordering::Relaxed);
+                    })
+                    .with_policy(policy)
+                    .sum();
+                assert_eq!(seen.into_inner(), s);
+                assert_eq!(s, (0..size).sum());
+            }
+        }
+    }
+
+    /// Check `(state, count)` pairs come in runs sharing a state and counting from 1.
+    /// Returns the number of runs.
+    fn state_runs(v: &[(usize, usize)]) -> usize {
+        let mut runs = 0;
+        for (i, &(state, count)) in v.iter().enumerate() {
+            if count == 1 {
+                runs += 1;
+            } else {
+                assert_eq!((state, count), (v[i - 1].0, v[i - 1].1 + 1));
+            }
+        }
+        runs
+    }
+
+    #[test]
+    fn map_states_are_local_to_divisions() {
+        for &size in &SIZES {
+            for policy in policies() {
+                // all divisions start from a clone of the initial state
+                let v: Vec<(usize, usize)> = (0..size)
+                    .into_adapt_iter()
+                    .map_with(0, |count, _| {
+                        *count += 1;
+                        (0, *count)
+                    })
+                    .with_policy(policy)
+                    .collect();
+                assert_eq!(v.len(), size);
+                let runs = state_runs(&v);
+                let inits = AtomicUsize::new(0);
+                let v: Vec<(usize, usize)> = (0..size)
+                    .into_adapt_iter()
+                    .map_init(
+                        || (inits.fetch_add(1, Ordering::SeqCst), 0),
+                        |(id, count), _| {
+                            *count += 1;
+                            (*id, *count)
+                        },
+                    )
+                    .with_policy(policy)
+                    .collect();
+                assert_eq!(v.len(), size);
+                let init_runs = state_runs(&v);
+                // empty divisions might also get a state
+                assert!(init_runs <= inits.into_inner());
+                if let Policy::Sequential = policy {
+                    assert_eq!(runs, size.min(1));
+                    assert_eq!(init_runs, size.min(1));
+                }
+                if let Policy::Join(1) = policy {
+                    assert_eq!(runs, size);
+                    assert_eq!(init_runs, size);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reversed_divisions_reach_both_ends() {
+        for &size in &SIZES {
+            let v: Vec<usize> = (0..size).collect();
+            for &index in &[0, 1, size / 2, size] {
+                let index = index.min(size);
+                let (left, right) = (0..size).divide_at_from_end(index);
+                assert_eq!((left, right), (0..(size - index), (size - index)..size));
+                let (left, right) = v.into_adapt_iter().map(|e| 2 * e).divide_at_from_end(index);
+                assert_eq!(right.base_length(), index);
+                assert!(left.into_iter().chain(right).eq((0..size).map(|e| 2 * e)));
+                // reversed inputs are divided from their end
+                let (left, right) = (0..size).into_adapt_iter().rev().divide_at(index);
+                assert!(left.into_iter().eq(((size - index)..size).rev()));
+                assert!(right.into_iter().eq((0..(size - index)).rev()));
+            }
+        }
+    }
+
+    #[test]
+    fn searches_find_both_ends() {
+        for &size in &SIZES {
+            let v: Vec<usize> = (0..size).collect();
+            for policy in policies() {
+                for &target in &[0, size.saturating_sub(1), size] {
+                    let found = v
+                        .into_adapt_iter()
+                        .with_policy(policy)
+                        .find_last(|&&e| e == target);
+                    assert_eq!(found, v.iter().rfind(|&&e| e == target));
+                    let position = v
+                        .into_adapt_iter()
+                        .with_policy(policy)
+                        .rposition(|&&e| e == target);
+                    assert_eq!(position, v.iter().rposition(|&e| e == target));
+                    // reversing twice gives back the original order
+                    let found = (0..size)
+                        .into_adapt_iter()
+                        .rev()
+                        .with_policy(policy)
+                        .find_last(|&e| e <= target);
+                    assert_eq!(found, (0..size).rev().rfind(|&e| e <= target));
+                    let position = (0..size)
+                        .into_adapt_iter()
+                        .rev()
+                        .with_policy(policy)
+                        .rposition(|&e| e >= target);
+                    assert_eq!(position, (0..size).rev().rposition(|e| e >= target));
+                    // forward searches share the blocks sizes
+                    let found = v
+                        .into_adapt_iter()
+                        .with_policy(policy)
+                        .find_first(|&&e| e == target);
+                    assert_eq!(found, v.iter().find(|&&e| e == target));
+                    let all = v
+                        .into_adapt_iter()
+                        .with_policy(policy)
+                        .all(|&e| e != target);
+                    assert_eq!(all, v.iter().all(|&e| e != target));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_inputs_reduce_to_identities() {
+        for policy in policies() {
+            // with and without blocks sizes, the identity is still converted
+            for sizes in [vec![], vec![0, 3], vec![10; 3]] {
+                let folded = (0..0)
+                    .into_adapt_iter()
+                    .with_policy(policy)
+                    .by_blocks(sizes.clone().into_iter())
+                    .fold_with(7, |a, e| a + e)
+                    .reduce(|a, b| a + b);
+                assert_eq!(folded, 7);
+                let reduced = ""
+                    .adapt_lines()
+                    .map(str::len)
+                    .with_policy(policy)
+                    .by_blocks(sizes.into_iter())
+                    .reduce_with(|a, b| a + b);
+                assert_eq!(reduced, None);
+            }
+            let words: Vec<&str> = (0..0)
+                .into_adapt_iter()
+                .flat_map(|i| i..(i + 2))
+                .with_policy(policy)
+                .fold_with(Vec::new(), |mut v, _| {
+                    v.push("never");
+                    v
+                })
+                .reduce(|mut a, b| {
+                    a.extend(b);
+                    a
+                });
+            assert!(words.is_empty());
+            let reduced = (0..0)
+                .into_adapt_iter()
+                .filter(|e| e % 2 == 1)
+                .with_policy(policy)
+                .reduce_with(|a, b| a + b);
+            assert_eq!(reduced, None);
+        }
+    }
+
+    #[test]
+    fn rev_and_chain_match_std() {
+        for &size in &SIZES {
+            for policy in policies() {
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .rev()
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).rev()));
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .map(|e| 2 * e)
+                    .rev()
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).map(|e| 2 * e).rev()));
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .chain((0..size / 3).into_adapt_iter())
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size).chain(0..size / 3)));
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .chain((size..2 * size).into_adapt_iter())
+                    .rev()
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..2 * size).rev()));
+                // blocked chains
+                let v: Vec<usize> = (0..size)
+                    .into_adapt_iter()
+                    .filter(|e| e % 2 == 0)
+                    .chain((0..size).into_adapt_iter().filter(|e| e % 3 == 0))
+                    .with_policy(policy)
+                    .collect();
+                assert!(v.into_iter().eq((0..size)
+                    .filter(|e| e % 2 == 0)
+                    .chain((0..size).filter(|e| e % 3 == 0))));
+            }
+        }
+    }
+
+    #[test]
+    fn reductions_match_std() {
+        for &size in &SIZES {
+            // lots of ties: keys repeat every 10 elements
+            let v: Vec<(usize, usize)> = (0..size).map(|i| (i % 10, i)).collect();
+            for policy in policies() {
+                let runner = || v.into_adapt_iter().with_policy(policy);
+                // std returns the first minimum and the last maximum, so do we
+                assert_eq!(runner().min_by_key(|p| p.0), v.iter().min_by_key(|p| p.0));
+                assert_eq!(runner().max_by_key(|p| p.0), v.iter().max_by_key(|p| p.0));
+                assert_eq!(
+                    runner().min_by(|a, b| a.0.cmp(&b.0)),
+                    v.iter().min_by(|a, b| a.0.cmp(&b.0))
+                );
+                assert_eq!(
+                    runner().max_by(|a, b| a.0.cmp(&b.0)),
+                    v.iter().max_by(|a, b| a.0.cmp(&b.0))
+                );
+                let factor = |i: usize| if i % 500 == 7 { 3u64 } else { 1 };
+                let product: u64 = (0..size)
+                    .into_adapt_iter()
+                    .map(factor)
+                    .with_policy(policy)
+                    .product();
+                assert_eq!(product, (0..size).map(factor).product());
+                let (even, odd): (Vec<usize>, Vec<usize>) = (0..size)
+                    .into_adapt_iter()
+                    .with_policy(policy)
+                    .partition(|e| e % 2 == 0);
+                let (expected_even, expected_odd): (Vec<usize>, Vec<usize>) =
+                    (0..size).partition(|e| e % 2 == 0);
+                assert_eq!((even, odd), (expected_even, expected_odd));
+                let (keys, values): (Vec<usize>, Vec<usize>) =
+                    v.into_adapt_iter().cloned().with_policy(policy).unzip();
+                let (expected_keys, expected_values): (Vec<usize>, Vec<usize>) =
+                    v.iter().cloned().unzip();
+                assert_eq!((keys, values), (expected_keys, expected_values));
+            }
+        }
+    }
+}
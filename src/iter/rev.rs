@@ -0,0 +1,48 @@
+use crate::prelude::*;
+use std::iter;
+
+/// Iterator adaptor reversing the direction of an adaptive iterator.
+/// Left and right parts get swapped on each division.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Rev<I: AdaptiveIterator> {
+    pub(crate) base: I,
+}
+
+impl<I: AdaptiveIterator> Divisible for Rev<I> {
+    type Power = I::Power;
+    fn base_length(&self) -> usize {
+        self.base.base_length()
+    }
+    fn divide(self) -> (Self, Self) {
+        let (left, right) = self.base.divide();
+        (Rev { base: right }, Rev { base: left })
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        let (left, right) = self.base.divide_ratio(denominator - numerator, denominator);
+        (Rev { base: right }, Rev { base: left })
+    }
+}
+
+impl<I: AdaptiveIterator> DivisibleIntoBlocks for Rev<I> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.base.divide_at_from_end(index);
+        (Rev { base: right }, Rev { base: left })
+    }
+}
+
+impl<I: AdaptiveIterator> IntoIterator for Rev<I>
+where
+    I::IntoIter: DoubleEndedIterator,
+{
+    type Item = I::Item;
+    type IntoIter = iter::Rev<I::IntoIter>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.base.into_iter().rev()
+    }
+}
+
+impl<I: AdaptiveIterator> AdaptiveIterator for Rev<I> where I::IntoIter: DoubleEndedIterator {}
+impl<I: AdaptiveIndexedIterator> AdaptiveIndexedIterator for Rev<I> where
+    I::IntoIter: DoubleEndedIterator
+{
+}
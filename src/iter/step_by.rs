@@ -0,0 +1,54 @@
+use crate::prelude::*;
+use crate::traits::IndexedPower;
+use std::cmp::min;
+use std::iter;
+
+/// Iterator adaptor yielding one element out of `step`, starting with the first one.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct StepBy<I: AdaptiveIndexedIterator> {
+    pub(crate) base: I,
+    pub(crate) step: usize,
+}
+
+impl<I: AdaptiveIndexedIterator> Divisible for StepBy<I> {
+    type Power = IndexedPower;
+    fn base_length(&self) -> usize {
+        self.base.base_length().div_ceil(self.step)
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<I: AdaptiveIndexedIterator> DivisibleIntoBlocks for StepBy<I> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        // we cut on a multiple of step so that the right part starts with a taken element
+        let base_index = min(index * self.step, self.base.base_length());
+        let (left, right) = self.base.divide_at(base_index);
+        (
+            StepBy {
+                base: left,
+                step: self.step,
+            },
+            StepBy {
+                base: right,
+                step: self.step,
+            },
+        )
+    }
+}
+
+impl<I: AdaptiveIndexedIterator> IntoIterator for StepBy<I> {
+    type Item = I::Item;
+    type IntoIter = iter::StepBy<I::IntoIter>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.base.into_iter().step_by(self.step)
+    }
+}
+
+impl<I: AdaptiveIndexedIterator> AdaptiveIterator for StepBy<I> {}
+impl<I: AdaptiveIndexedIterator> AdaptiveIndexedIterator for StepBy<I> {}
@@ -1,11 +1,57 @@
+//! Adaptive iterators on strings.
+//!
+//! All of them cut strings near the requested index, on the closest position
+//! which does not break what we iterate on (a character, a line, a word...).
 use crate::prelude::*;
-use crate::traits::BlockedPower;
+use crate::traits::{BlockedPower, IndexedPower};
+use crate::utils::ratio_index;
 use itertools::Itertools;
-use std::str::Chars;
+use std::cmp::{max, min};
+use std::iter::Copied;
+use std::slice;
+use std::str::{Chars, Lines, SplitWhitespace};
+
+/// Find the cutting index closest to `start_index` satisfying given predicate.
+/// Both ends of the string are always valid cuts but we only cut there if asked to
+/// (cutting a non-empty block there would make no progress).
+/// We return `None` if no other index is found.
+fn find_index_around<F: Fn(usize) -> bool>(
+    len: usize,
+    start_index: usize,
+    predicate: F,
+) -> Option<usize> {
+    if start_index == 0 || start_index >= len {
+        return Some(min(start_index, len));
+    }
+    let higher_indices = start_index..len;
+    let lower_indices = (1..start_index).rev();
+    higher_indices
+        .interleave(lower_indices)
+        .find(|&i| predicate(i))
+}
+
+/// Return the char boundary closest to `index`, at most 3 bytes away since utf8 chars
+/// are at most 4 bytes long.
+/// We look backward first. Cutting at one end of a non-empty string would make
+/// no progress so we only return ends if asked to and return `None` when no
+/// other boundary is found (the string is a single char).
+fn char_boundary_around(s: &str, index: usize) -> Option<usize> {
+    if index == 0 || index >= s.len() {
+        return Some(min(index, s.len()));
+    }
+    let lowest = max(index.saturating_sub(3), 1);
+    (lowest..=index)
+        .rev()
+        .chain((index + 1)..min(index + 4, s.len()))
+        .find(|&i| s.is_char_boundary(i))
+}
 
 /// Adaptive iterator on characters of strings.
+/// Its length is the number of chars (counted once, when created)
+/// and not the number of bytes.
 pub struct AdaptiveChars<'a> {
     real_str: &'a str,
+    chars_count: usize,
 }
 
 impl<'a> IntoIterator for AdaptiveChars<'a> {
@@ -17,53 +63,688 @@ impl<'a> IntoIterator for AdaptiveChars<'a> {
 }
 
 impl<'a> AdaptiveChars<'a> {
-    fn find_splitting_index_around(&self, start_index: usize) -> usize {
-        let len = self.real_str.len();
-        let higher_indices = start_index..len;
-        let lower_indices = (0..start_index).rev();
-        lower_indices
-            .interleave(higher_indices)
-            .find(|&i| self.real_str.is_char_boundary(i))
-            .unwrap_or(len) // if we cannot split we just generate an empty slice
+    fn new(real_str: &'a str) -> Self {
+        AdaptiveChars {
+            real_str,
+            chars_count: real_str.chars().count(),
+        }
+    }
+    /// Cut at given byte (which needs to be a char boundary),
+    /// knowing the number of chars on the left.
+    fn split_at(self, byte_index: usize, left_count: usize) -> (Self, Self) {
+        let (left, right) = self.real_str.split_at(byte_index);
+        (
+            AdaptiveChars {
+                real_str: left,
+                chars_count: left_count,
+            },
+            AdaptiveChars {
+                real_str: right,
+                chars_count: self.chars_count - left_count,
+            },
+        )
+    }
+    /// Cut near given byte: we then only need to count the chars on the left.
+    fn split_around(self, byte_index: usize) -> (Self, Self) {
+        let index = char_boundary_around(self.real_str, byte_index).unwrap_or(self.real_str.len());
+        let left_count = self.real_str[..index].chars().count();
+        self.split_at(index, left_count)
+    }
+    /// Byte position of the `index`th char.
+    /// We iterate from the closest end.
+    fn char_position(&self, index: usize) -> usize {
+        if index >= self.chars_count {
+            self.real_str.len()
+        } else if 2 * index <= self.chars_count {
+            self.real_str.char_indices().nth(index).unwrap().0
+        } else {
+            self.real_str
+                .char_indices()
+                .rev()
+                .nth(self.chars_count - index - 1)
+                .unwrap()
+                .0
+        }
     }
 }
 
 impl<'a> Divisible for AdaptiveChars<'a> {
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.chars_count
+    }
+    /// Cut in the middle in bytes.
+    fn divide(self) -> (Self, Self) {
+        let mid = self.real_str.len() / 2;
+        self.split_around(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        let index = ratio_index(self.real_str.len(), numerator, denominator);
+        self.split_around(index)
+    }
+}
+
+impl<'a> DivisibleIntoBlocks for AdaptiveChars<'a> {
+    /// Put `index` chars on the left.
+    /// This costs as much as iterating on the smallest part.
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let index = min(index, self.chars_count);
+        let byte_index = self.char_position(index);
+        self.split_at(byte_index, index)
+    }
+    /// Put `index` chars on the right.
+    fn divide_at_from_end(self, index: usize) -> (Self, Self) {
+        let left_count = self.chars_count.saturating_sub(index);
+        self.divide_at(left_count)
+    }
+}
+
+impl<'a> AdaptiveIterator for AdaptiveChars<'a> {}
+
+/// Adaptive iterator on characters of strings, together with their byte positions.
+pub struct AdaptiveCharIndices<'a> {
+    chars: AdaptiveChars<'a>,
+    // position of our slice inside the original string
+    offset: usize,
+}
+
+/// Sequential iterator for `AdaptiveCharIndices`.
+pub struct CharIndices<'a> {
+    iterator: std::str::CharIndices<'a>,
+    offset: usize,
+}
+
+impl<'a> Iterator for CharIndices<'a> {
+    type Item = (usize, char);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next().map(|(i, c)| (i + self.offset, c))
+    }
+}
+
+impl<'a> IntoIterator for AdaptiveCharIndices<'a> {
+    type Item = (usize, char);
+    type IntoIter = CharIndices<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        CharIndices {
+            iterator: self.chars.real_str.char_indices(),
+            offset: self.offset,
+        }
+    }
+}
+
+impl<'a> AdaptiveCharIndices<'a> {
+    /// Rebuild both parts from divided chars.
+    fn from_parts(
+        offset: usize,
+        (left, right): (AdaptiveChars<'a>, AdaptiveChars<'a>),
+    ) -> (Self, Self) {
+        let right_offset = offset + left.real_str.len();
+        (
+            AdaptiveCharIndices {
+                chars: left,
+                offset,
+            },
+            AdaptiveCharIndices {
+                chars: right,
+                offset: right_offset,
+            },
+        )
+    }
+}
+
+impl<'a> Divisible for AdaptiveCharIndices<'a> {
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.chars.base_length()
+    }
+    fn divide(self) -> (Self, Self) {
+        AdaptiveCharIndices::from_parts(self.offset, self.chars.divide())
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<'a> DivisibleIntoBlocks for AdaptiveCharIndices<'a> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        AdaptiveCharIndices::from_parts(self.offset, self.chars.divide_at(index))
+    }
+}
+
+impl<'a> AdaptiveIterator for AdaptiveCharIndices<'a> {}
+
+/// Adaptive iterator on the bytes of strings.
+pub struct AdaptiveBytes<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> IntoIterator for AdaptiveBytes<'a> {
+    type Item = u8;
+    type IntoIter = Copied<slice::Iter<'a, u8>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.bytes.iter().copied()
+    }
+}
+
+impl<'a> Divisible for AdaptiveBytes<'a> {
+    type Power = IndexedPower;
+    fn base_length(&self) -> usize {
+        self.bytes.len()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<'a> DivisibleIntoBlocks for AdaptiveBytes<'a> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.bytes.split_at(index);
+        (
+            AdaptiveBytes { bytes: left },
+            AdaptiveBytes { bytes: right },
+        )
+    }
+}
+
+impl<'a> AdaptiveIterator for AdaptiveBytes<'a> {}
+impl<'a> AdaptiveIndexedIterator for AdaptiveBytes<'a> {}
+
+/// Adaptive iterator on the lines of strings (see `str::lines`).
+/// We only cut right after line feeds.
+pub struct AdaptiveLines<'a> {
+    real_str: &'a str,
+}
+
+impl<'a> IntoIterator for AdaptiveLines<'a> {
+    type Item = &'a str;
+    type IntoIter = Lines<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.real_str.lines()
+    }
+}
+
+impl<'a> Divisible for AdaptiveLines<'a> {
     type Power = BlockedPower;
     fn base_length(&self) -> usize {
         self.real_str.len()
     }
-    /// Pre-condition: self.can_be_divided() is true.
     fn divide(self) -> (Self, Self) {
-        let index = self.find_splitting_index_around(self.real_str.len() / 2);
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<'a> AdaptiveLines<'a> {
+    /// Cut at the line start closest to `index` or at `fallback` if there is none.
+    fn split_around(self, index: usize, fallback: usize) -> (Self, Self) {
+        let bytes = self.real_str.as_bytes();
+        let index =
+            find_index_around(bytes.len(), index, |i| bytes[i - 1] == b'\n').unwrap_or(fallback);
         let (left, right) = self.real_str.split_at(index);
         (
-            AdaptiveChars { real_str: left },
-            AdaptiveChars { real_str: right },
+            AdaptiveLines { real_str: left },
+            AdaptiveLines { real_str: right },
         )
     }
 }
 
-impl<'a> DivisibleIntoBlocks for AdaptiveChars<'a> {
-    /// Pre-condition: self.can_be_divided() is true.
+impl<'a> DivisibleIntoBlocks for AdaptiveLines<'a> {
+    /// If we cannot cut, everything goes to the left.
     fn divide_at(self, index: usize) -> (Self, Self) {
-        let index = self.find_splitting_index_around(index);
-        let (left, right) = self.real_str.split_at(index);
+        let len = self.real_str.len();
+        self.split_around(index, len)
+    }
+    /// If we cannot cut, everything goes to the right (for reversed iterations to progress).
+    fn divide_at_from_end(self, index: usize) -> (Self, Self) {
+        let len = self.real_str.len();
+        self.split_around(len.saturating_sub(index), 0)
+    }
+}
+
+impl<'a> AdaptiveIterator for AdaptiveLines<'a> {}
+
+/// Adaptive iterator on the words of strings (see `str::split_whitespace`).
+/// We only cut right before whitespaces.
+pub struct AdaptiveSplitWhitespace<'a> {
+    real_str: &'a str,
+}
+
+impl<'a> IntoIterator for AdaptiveSplitWhitespace<'a> {
+    type Item = &'a str;
+    type IntoIter = SplitWhitespace<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.real_str.split_whitespace()
+    }
+}
+
+impl<'a> Divisible for AdaptiveSplitWhitespace<'a> {
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.real_str.len()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<'a> AdaptiveSplitWhitespace<'a> {
+    /// Cut at the whitespace closest to `index` or at `fallback` if there is none.
+    fn split_around(self, index: usize, fallback: usize) -> (Self, Self) {
+        let s = self.real_str;
+        let index = find_index_around(s.len(), index, |i| {
+            s.is_char_boundary(i) && s[i..].starts_with(char::is_whitespace)
+        })
+        .unwrap_or(fallback);
+        let (left, right) = s.split_at(index);
         (
-            AdaptiveChars { real_str: left },
-            AdaptiveChars { real_str: right },
+            AdaptiveSplitWhitespace { real_str: left },
+            AdaptiveSplitWhitespace { real_str: right },
         )
     }
 }
 
-impl<'a> AdaptiveIterator for AdaptiveChars<'a> {}
+impl<'a> DivisibleIntoBlocks for AdaptiveSplitWhitespace<'a> {
+    /// If we cannot cut, everything goes to the left.
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let len = self.real_str.len();
+        self.split_around(index, len)
+    }
+    /// If we cannot cut, everything goes to the right (for reversed iterations to progress).
+    fn divide_at_from_end(self, index: usize) -> (Self, Self) {
+        let len = self.real_str.len();
+        self.split_around(len.saturating_sub(index), 0)
+    }
+}
+
+impl<'a> AdaptiveIterator for AdaptiveSplitWhitespace<'a> {}
+
+/// Separators for `adapt_split`: a `char` or a predicate on chars.
+pub trait CharPattern: Clone + Send + Sync {
+    /// Is given char a separator ?
+    fn matches(&self, c: char) -> bool;
+}
+
+impl CharPattern for char {
+    fn matches(&self, c: char) -> bool {
+        *self == c
+    }
+}
+
+impl<F: Fn(char) -> bool + Clone + Send + Sync> CharPattern for F {
+    fn matches(&self, c: char) -> bool {
+        self(c)
+    }
+}
+
+/// Adaptive iterator on substrings separated by a pattern (see `str::split`).
+/// We only cut right after separators.
+///
+/// Unlike `str::split` it cannot be reversed: its sequential iterator
+/// is not a `DoubleEndedIterator`.
+///
+/// ```compile_fail
+/// use rayon_adaptive::prelude::*;
+/// let fields: Vec<&str> = "a,b,c".adapt_split(',').rev().collect();
+/// ```
+pub struct AdaptiveSplit<'a, P> {
+    real_str: &'a str,
+    pattern: P,
+    // is the text after our last separator the end of the last substring ?
+    // this is false for all left parts since their last substring continues on the right.
+    last: bool,
+}
+
+/// Sequential iterator for `AdaptiveSplit`.
+pub struct Split<'a, P> {
+    remaining: Option<&'a str>,
+    pattern: P,
+    last: bool,
+}
+
+impl<'a, P: CharPattern> Iterator for Split<'a, P> {
+    type Item = &'a str;
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.remaining?;
+        let pattern = &self.pattern;
+        match s.char_indices().find(|&(_, c)| pattern.matches(c)) {
+            Some((i, c)) => {
+                self.remaining = Some(&s[(i + c.len_utf8())..]);
+                Some(&s[..i])
+            }
+            None => {
+                self.remaining = None;
+                if self.last {
+                    Some(s)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl<'a, P: CharPattern> IntoIterator for AdaptiveSplit<'a, P> {
+    type Item = &'a str;
+    type IntoIter = Split<'a, P>;
+    fn into_iter(self) -> Self::IntoIter {
+        Split {
+            remaining: Some(self.real_str),
+            pattern: self.pattern,
+            last: self.last,
+        }
+    }
+}
+
+impl<'a, P: CharPattern> Divisible for AdaptiveSplit<'a, P> {
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.real_str.len()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<'a, P: CharPattern> DivisibleIntoBlocks for AdaptiveSplit<'a, P> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let s = self.real_str;
+        let pattern = &self.pattern;
+        let index = find_index_around(s.len(), index, |i| {
+            s.is_char_boundary(i)
+                && s[..i]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| pattern.matches(c))
+        })
+        .unwrap_or(s.len());
+        let (left, right) = s.split_at(index);
+        // unless we could not find any separator, left part ends right after one
+        let left_is_last = self.last && index == s.len();
+        let right_is_last = self.last && index != s.len();
+        (
+            AdaptiveSplit {
+                real_str: left,
+                pattern: self.pattern.clone(),
+                last: left_is_last,
+            },
+            AdaptiveSplit {
+                real_str: right,
+                pattern: self.pattern,
+                last: right_is_last,
+            },
+        )
+    }
+}
+
+impl<'a, P: CharPattern> AdaptiveIterator for AdaptiveSplit<'a, P> {}
 
 pub trait AdaptiveString {
-    fn adapt_chars(&self) -> AdaptiveChars;
+    fn adapt_chars(&self) -> AdaptiveChars<'_>;
+    /// Adaptive iterator on characters and their byte positions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let s = "aé€b".repeat(100);
+    /// let positions: Vec<_> = s.adapt_char_indices().collect();
+    /// assert_eq!(positions, s.char_indices().collect::<Vec<_>>());
+    /// ```
+    fn adapt_char_indices(&self) -> AdaptiveCharIndices<'_>;
+    /// Adaptive iterator on bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let s = "hello world";
+    /// assert_eq!(s.adapt_bytes().filter(|&b| b == b'o').count(), 2);
+    /// ```
+    fn adapt_bytes(&self) -> AdaptiveBytes<'_>;
+    /// Adaptive iterator on lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let log = "GET /index\r\nPOST /login\n\nGET /about\n".repeat(100);
+    /// let lines: Vec<&str> = log.adapt_lines().collect();
+    /// assert_eq!(lines, log.lines().collect::<Vec<_>>());
+    /// assert_eq!(log.adapt_lines().filter(|l| l.starts_with("GET")).count(), 200);
+    /// ```
+    fn adapt_lines(&self) -> AdaptiveLines<'_>;
+    /// Adaptive iterator on substrings separated by given char or char predicate.
+    /// Unlike `str::split`, an empty string yields no substring at all.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let csv = "1,22,,333,".repeat(100);
+    /// let fields: Vec<&str> = csv.adapt_split(',').collect();
+    /// assert_eq!(fields, csv.split(',').collect::<Vec<_>>());
+    /// let total: usize = csv
+    ///     .adapt_split(|c: char| c == ',')
+    ///     .filter_map(|f| f.parse::<usize>().ok())
+    ///     .sum();
+    /// assert_eq!(total, 356 * 100);
+    /// ```
+    fn adapt_split<P: CharPattern>(&self, pattern: P) -> AdaptiveSplit<'_, P>;
+    /// Adaptive iterator on whitespace separated words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// let text = "the quick  brown\tfox\njumps ".repeat(100);
+    /// let words: Vec<&str> = text.adapt_split_whitespace().collect();
+    /// assert_eq!(words, text.split_whitespace().collect::<Vec<_>>());
+    /// ```
+    fn adapt_split_whitespace(&self) -> AdaptiveSplitWhitespace<'_>;
 }
 
 impl AdaptiveString for str {
-    fn adapt_chars(&self) -> AdaptiveChars {
-        AdaptiveChars { real_str: self }
+    fn adapt_chars(&self) -> AdaptiveChars<'_> {
+        AdaptiveChars::new(self)
+    }
+    fn adapt_char_indices(&self) -> AdaptiveCharIndices<'_> {
+        AdaptiveCharIndices {
+            chars: AdaptiveChars::new(self),
+            offset: 0,
+        }
+    }
+    fn adapt_bytes(&self) -> AdaptiveBytes<'_> {
+        AdaptiveBytes {
+            bytes: self.as_bytes(),
+        }
+    }
+    fn adapt_lines(&self) -> AdaptiveLines<'_> {
+        AdaptiveLines { real_str: self }
+    }
+    fn adapt_split<P: CharPattern>(&self, pattern: P) -> AdaptiveSplit<'_, P> {
+        AdaptiveSplit {
+            real_str: self,
+            pattern,
+            last: true,
+        }
+    }
+    fn adapt_split_whitespace(&self) -> AdaptiveSplitWhitespace<'_> {
+        AdaptiveSplitWhitespace { real_str: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Policy;
+
+    const MULTILINGUAL: &str = "Hello wörld! Ça va ? Привет, мир. 你好，世界。\
+                                مرحبا بالعالم. नमस्ते दुनिया। 🦀🚀 ε = mc² ∀x∈ℝ.";
+
+    fn policies() -> Vec<Policy> {
+        vec![
+            Policy::Sequential,
+            Policy::Join(1),
+            Policy::JoinContext(2),
+            Policy::JoinRatio(1, 1, 3),
+            Policy::DepJoin(3),
+            Policy::Adaptive(1, 8),
+            Policy::DefaultPolicy,
+        ]
+    }
+
+    #[test]
+    fn boundaries_are_found_nearby() {
+        let s = MULTILINGUAL;
+        for index in 1..s.len() {
+            let boundary = char_boundary_around(s, index).unwrap();
+            assert!(s.is_char_boundary(boundary));
+            assert!(boundary > 0 && boundary < s.len());
+            assert!(boundary + 3 >= index && boundary <= index + 3);
+        }
+        assert_eq!(char_boundary_around(s, 0), Some(0));
+        assert_eq!(char_boundary_around(s, s.len() + 1), Some(s.len()));
+        // a single char cannot be divided
+        assert_eq!(char_boundary_around("🦀", 2), None);
+    }
+
+    #[test]
+    fn single_chars_go_to_the_right_when_cut_from_the_end() {
+        let (left, right) = "🦀".adapt_chars().divide_at(1);
+        assert_eq!((left.real_str, right.real_str), ("🦀", ""));
+        let (left, right) = "🦀".adapt_chars().divide_at_from_end(1);
+        assert_eq!((left.real_str, right.real_str), ("", "🦀"));
+        for policy in policies() {
+            let reversed: String = "🦀é".adapt_chars().rev().with_policy(policy).collect();
+            assert_eq!(reversed, "é🦀");
+        }
+    }
+
+    #[test]
+    fn lengths_count_chars() {
+        let s = MULTILINGUAL.repeat(10);
+        let chars_count = s.chars().count();
+        let chars = s.adapt_chars();
+        assert_eq!(chars.base_length(), chars_count);
+        for index in 0..=chars_count + 1 {
+            let (left, right) = s.adapt_chars().divide_at(index);
+            let left_count = min(index, chars_count);
+            assert_eq!(left.base_length(), left_count);
+            assert_eq!(left.real_str.chars().count(), left_count);
+            assert_eq!(right.real_str.chars().count(), chars_count - left_count);
+            let (left, right) = s.adapt_chars().divide_at_from_end(index);
+            assert_eq!(right.base_length(), left_count);
+            assert_eq!(right.real_str.chars().count(), left_count);
+            assert_eq!(left.real_str.chars().count(), chars_count - left_count);
+        }
+        let (left, right) = chars.divide();
+        assert!(left.base_length() > 0 && right.base_length() > 0);
+        assert_eq!(left.base_length(), left.real_str.chars().count());
+        assert_eq!(right.base_length(), right.real_str.chars().count());
+        assert_eq!(left.base_length() + right.base_length(), chars_count);
+        let (left, right) = s.adapt_chars().divide_ratio(1, 4);
+        assert!(left.base_length() > 0 && right.base_length() > 0);
+        assert_eq!(left.base_length(), left.real_str.chars().count());
+        assert_eq!(left.base_length() + right.base_length(), chars_count);
+    }
+
+    #[test]
+    fn multilingual_chars() {
+        for repetitions in [1, 7, 100] {
+            let s = MULTILINGUAL.repeat(repetitions);
+            for policy in policies() {
+                let chars: Vec<char> = s.adapt_chars().with_policy(policy).collect();
+                assert_eq!(chars, s.chars().collect::<Vec<char>>());
+                let reversed: String = s.adapt_chars().rev().with_policy(policy).collect();
+                assert_eq!(reversed, s.chars().rev().collect::<String>());
+                let indices: Vec<(usize, char)> =
+                    s.adapt_char_indices().with_policy(policy).collect();
+                assert_eq!(indices, s.char_indices().collect::<Vec<_>>());
+                assert_eq!(
+                    s.adapt_chars()
+                        .with_policy(policy)
+                        .find_first(|&c| c == '🚀'),
+                    Some('🚀')
+                );
+                assert_eq!(
+                    s.adapt_chars()
+                        .filter(|c| c.is_alphabetic())
+                        .with_policy(policy)
+                        .count(),
+                    s.chars().filter(|c| c.is_alphabetic()).count()
+                );
+            }
+        }
+    }
+
+    fn texts() -> Vec<String> {
+        vec![
+            String::new(),
+            "no line feed at all".to_string(),
+            "\n".to_string(),
+            "  \t ".to_string(),
+            MULTILINGUAL.to_string(),
+            "one\ntwo\r\n\nthree".repeat(50),
+            "a, b ,,c\n 🦀,\t,".repeat(500),
+            MULTILINGUAL.replace(' ', "\n").repeat(100),
+        ]
+    }
+
+    #[test]
+    fn strings_iterators_match_std() {
+        for text in texts() {
+            let s = text.as_str();
+            for policy in policies() {
+                let lines: Vec<&str> = s.adapt_lines().with_policy(policy).collect();
+                assert_eq!(lines, s.lines().collect::<Vec<_>>());
+                let reversed_lines: Vec<&str> = s.adapt_lines().rev().with_policy(policy).collect();
+                assert_eq!(reversed_lines, s.lines().rev().collect::<Vec<_>>());
+                let words: Vec<&str> = s.adapt_split_whitespace().with_policy(policy).collect();
+                assert_eq!(words, s.split_whitespace().collect::<Vec<_>>());
+                let reversed_words: Vec<&str> = s
+                    .adapt_split_whitespace()
+                    .rev()
+                    .with_policy(policy)
+                    .collect();
+                assert_eq!(
+                    reversed_words,
+                    s.split_whitespace().rev().collect::<Vec<_>>()
+                );
+                // we yield nothing on empty strings
+                if !s.is_empty() {
+                    let fields: Vec<&str> = s.adapt_split(',').with_policy(policy).collect();
+                    assert_eq!(fields, s.split(',').collect::<Vec<_>>());
+                    let fields: Vec<&str> = s
+                        .adapt_split(char::is_whitespace)
+                        .with_policy(policy)
+                        .collect();
+                    assert_eq!(fields, s.split(char::is_whitespace).collect::<Vec<_>>());
+                } else {
+                    assert_eq!(s.adapt_split(',').with_policy(policy).count(), 0);
+                }
+                let indices: Vec<(usize, char)> =
+                    s.adapt_char_indices().with_policy(policy).collect();
+                assert_eq!(indices, s.char_indices().collect::<Vec<_>>());
+                let bytes: Vec<u8> = s.adapt_bytes().with_policy(policy).collect();
+                assert_eq!(bytes, s.as_bytes());
+                let reversed_bytes: Vec<u8> = s.adapt_bytes().rev().with_policy(policy).collect();
+                assert!(reversed_bytes.into_iter().eq(s.bytes().rev()));
+            }
+        }
     }
 }
@@ -1,6 +1,6 @@
 use crate::prelude::*;
 use crate::traits::IndexedPower;
-use derive_divisible::{Divisible, DivisibleAtIndex, DivisibleIntoBlocks};
+use rayon_adaptive_derive::{Divisible, DivisibleAtIndex, DivisibleIntoBlocks};
 use std;
 use std::iter;
 
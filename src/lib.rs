@@ -10,21 +10,67 @@ extern crate rayon as real_rayon;
 extern crate rayon_logs as rayon;
 #[macro_use]
 extern crate smallvec;
+// derived code refers to rayon_adaptive, even inside this crate
+extern crate self as rayon_adaptive;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::channel;
 mod traits;
 pub use crate::traits::*;
+/// Derive `Divisible`, `DivisibleIntoBlocks` and `DivisibleAtIndex` field by field.
+///
+/// Each field is divided unless specified otherwise with
+/// `#[divide_by(clone)]`, `#[divide_by(default)]` or `#[divide_by(split_fn = "path")]`.
+/// Power is given with `#[power(...)]`.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::prelude::*;
+/// use rayon_adaptive::{Divisible, DivisibleIntoBlocks, IndexedPower};
+///
+/// #[derive(Divisible, DivisibleIntoBlocks)]
+/// #[power(IndexedPower)]
+/// struct Scaled<'a> {
+///     values: &'a [u32],
+///     #[divide_by(clone)]
+///     factor: u32,
+/// }
+///
+/// let v: Vec<u32> = (0..100).collect();
+/// let input = Scaled { values: &v, factor: 2 };
+/// let (left, right) = input.divide_at(30);
+/// assert_eq!(left.base_length(), 30);
+/// assert_eq!(right.factor, 2);
+/// assert_eq!(right.values[0], 30);
+/// ```
+pub use rayon_adaptive_derive::{Divisible, DivisibleAtIndex, DivisibleIntoBlocks};
 mod scheduling;
 pub mod utils;
 pub use crate::utils::fuse_slices;
 mod slices;
 pub use crate::slices::{EdibleSlice, EdibleSliceMut};
+#[cfg(unix)]
+mod mmap;
+#[cfg(unix)]
+pub use crate::mmap::{MmapFile, MmapInput, Records};
 mod activated_input;
 mod chunks;
 pub mod iter;
-// pub use crate::iter::hash::{par_elements, par_iter, par_keys};
+pub use crate::iter::hash::{
+    par_elements, par_iter, par_keys, par_values, par_values_mut, Buckets, BucketsMut,
+};
+pub use crate::iter::bridge::{Bridge, ParIter};
+pub use crate::iter::buffered::BufferedIterInput;
+pub use crate::iter::chain::Chain;
+pub use crate::iter::enumerate::Enumerate;
+pub use crate::iter::flat_map::{FlatMap, Flatten};
 pub use crate::iter::iter::Iter;
+pub use crate::iter::filter_map::FilterMap;
+pub use crate::iter::inspect::Inspect;
 pub use crate::iter::map::Map;
+pub use crate::iter::map_with::{MapInit, MapStateIter, MapWith};
+pub use crate::iter::rev::Rev;
+pub use crate::iter::step_by::StepBy;
 pub use crate::iter::zip::Zip;
 
 mod folders;
@@ -39,6 +85,7 @@ pub use smallchannel::{small_channel, SmallReceiver, SmallSender};
 mod algorithms;
 pub use crate::algorithms::infix_solvers::*;
 pub use crate::algorithms::prefix::adaptive_prefix;
+pub use crate::algorithms::segmented::{adaptive_segmented_prefix, segmented_reduce};
 pub use crate::algorithms::{
     merge_sort::adaptive_sort, merge_sort::adaptive_sort_with_policies,
     merge_sort_no_copy::adaptive_sort_no_copy_with_policies, merge_sort_raw::adaptive_sort_raw,
@@ -0,0 +1,271 @@
+//! We provide here `MmapFile` and `MmapInput` for scanning (large) files
+//! without reading them into memory first.
+
+use crate::prelude::*;
+use crate::traits::BlockedPower;
+use std::cmp::min;
+use std::fs::File;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::ptr;
+use std::slice;
+
+/// How files are cut into records.
+/// Inputs are only divided between two records.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Records {
+    /// Records end with a line feed (which is not part of the record).
+    Lines,
+    /// All records have the given size (in bytes), except maybe the last one.
+    Fixed(usize),
+}
+
+/// A file mapped read-only in memory.
+pub struct MmapFile {
+    data: *const u8,
+    len: usize,
+}
+
+// the mapping is read-only and only unmapped on drop.
+unsafe impl Send for MmapFile {}
+unsafe impl Sync for MmapFile {}
+
+impl MmapFile {
+    /// Map given file in memory.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated (by this process or any other one)
+    /// for as long as the mapping lives: the slices we hand out would change under
+    /// our feet, or point past the end of the file and raise SIGBUS.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len() as usize;
+        if len == 0 {
+            // we cannot map empty files
+            return Ok(MmapFile {
+                data: ptr::null(),
+                len,
+            });
+        }
+        let data = libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_READ,
+            libc::MAP_PRIVATE,
+            file.as_raw_fd(),
+            0,
+        );
+        if data == libc::MAP_FAILED {
+            Err(io::Error::last_os_error())
+        } else {
+            // the mapping stays valid once the file is closed
+            Ok(MmapFile {
+                data: data as *const u8,
+                len,
+            })
+        }
+    }
+    /// Return the file's content.
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.len == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.data, self.len) }
+        }
+    }
+    /// Return a divisible input on the whole file, cut between given records.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// use rayon_adaptive::{MmapFile, Records};
+    /// use std::io::Write;
+    ///
+    /// let path = std::env::temp_dir().join("rayon_adaptive_mmap_example.log");
+    /// let mut file = std::fs::File::create(&path).unwrap();
+    /// for i in 0..10_000 {
+    ///     writeln!(file, "{} {}", if i % 3 == 0 { "ERROR" } else { "INFO" }, i).unwrap();
+    /// }
+    /// drop(file);
+    ///
+    /// // nobody touches the file while we use it
+    /// let mapped = unsafe { MmapFile::open(&path) }.unwrap();
+    /// let errors = mapped
+    ///     .input(Records::Lines)
+    ///     .map_reduce(
+    ///         |i| i.into_iter().filter(|l| l.starts_with(b"ERROR")).count(),
+    ///         |a, b| a + b,
+    ///     );
+    /// assert_eq!(errors, 3334);
+    /// let first_info = mapped
+    ///     .input(Records::Lines)
+    ///     .find_first(|l| l.starts_with(b"INFO"));
+    /// assert_eq!(first_info, Some(&b"INFO 1"[..]));
+    /// std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn input(&self, records: Records) -> MmapInput<'_> {
+        if let Records::Fixed(size) = records {
+            assert!(size > 0, "records cannot be empty");
+        }
+        MmapInput {
+            bytes: self.as_bytes(),
+            records,
+        }
+    }
+}
+
+impl Drop for MmapFile {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe {
+                libc::munmap(self.data as *mut libc::c_void, self.len);
+            }
+        }
+    }
+}
+
+/// Part of a memory mapped file, containing only whole records.
+/// Its length is in bytes and iterating on it yields records.
+pub struct MmapInput<'a> {
+    bytes: &'a [u8],
+    records: Records,
+}
+
+impl<'a> MmapInput<'a> {
+    /// Return our content.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+    /// Return the end of the record containing byte at given index
+    /// (we never cut at 0 unless asked to).
+    fn record_end(&self, index: usize) -> usize {
+        let len = self.bytes.len();
+        if index == 0 || index >= len {
+            return min(index, len);
+        }
+        match self.records {
+            Records::Lines => self.bytes[(index - 1)..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|position| index + position)
+                .or_else(|| {
+                    // no more line feeds, try before
+                    self.bytes[..(index - 1)]
+                        .iter()
+                        .rposition(|&b| b == b'\n')
+                        .map(|position| position + 1)
+                })
+                .unwrap_or(len),
+            Records::Fixed(size) => min(index.div_ceil(size) * size, len),
+        }
+    }
+}
+
+impl<'a> Divisible for MmapInput<'a> {
+    type Power = BlockedPower;
+    fn base_length(&self) -> usize {
+        self.bytes.len()
+    }
+    fn divide(self) -> (Self, Self) {
+        let mid = self.base_length() / 2;
+        self.divide_at(mid)
+    }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
+}
+
+impl<'a> DivisibleIntoBlocks for MmapInput<'a> {
+    fn divide_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.bytes.split_at(self.record_end(index));
+        (
+            MmapInput {
+                bytes: left,
+                records: self.records,
+            },
+            MmapInput {
+                bytes: right,
+                records: self.records,
+            },
+        )
+    }
+}
+
+impl<'a> IntoIterator for MmapInput<'a> {
+    type Item = &'a [u8];
+    type IntoIter = RecordsIter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        RecordsIter {
+            remaining: self.bytes,
+            records: self.records,
+        }
+    }
+}
+
+impl<'a> AdaptiveIterator for MmapInput<'a> {}
+
+/// Sequential iterator on the records of a `MmapInput`.
+pub struct RecordsIter<'a> {
+    remaining: &'a [u8],
+    records: Records,
+}
+
+impl<'a> Iterator for RecordsIter<'a> {
+    type Item = &'a [u8];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let (record, remaining) = match self.records {
+            Records::Lines => match self.remaining.iter().position(|&b| b == b'\n') {
+                Some(end) => (&self.remaining[..end], &self.remaining[(end + 1)..]),
+                None => (self.remaining, &self.remaining[self.remaining.len()..]),
+            },
+            Records::Fixed(size) => self.remaining.split_at(min(size, self.remaining.len())),
+        };
+        self.remaining = remaining;
+        Some(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Policy;
+    use std::io::Write;
+
+    #[test]
+    fn mapped_files_are_scanned_in_parallel() {
+        let path = std::env::temp_dir().join(format!(
+            "rayon_adaptive_mmap_test_{}.txt",
+            std::process::id()
+        ));
+        let content: String = (0..50_000).map(|i| format!("{}\n", i % 7)).collect();
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+        let mapped = unsafe { MmapFile::open(&path) }.unwrap();
+        assert_eq!(mapped.as_bytes(), content.as_bytes());
+        for policy in &[
+            Policy::Sequential,
+            Policy::Join(100),
+            Policy::Adaptive(10, 1000),
+            Policy::DefaultPolicy,
+        ] {
+            let sixes = mapped
+                .input(Records::Lines)
+                .filter(|l| *l == b"6")
+                .with_policy(*policy)
+                .count();
+            assert_eq!(sixes, (0..50_000).filter(|i| i % 7 == 6).count());
+            let records = mapped.input(Records::Fixed(2)).with_policy(*policy).count();
+            assert_eq!(records, 50_000);
+        }
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -17,6 +17,12 @@ pub enum Policy {
     Join(usize),
     /// Recursively cut in two with join_context until given block size.
     JoinContext(usize),
+    /// Like `Join` but cut unevenly: left part gets `numerator / denominator`.
+    /// Parameters are block size, numerator and denominator.
+    JoinRatio(usize, usize, usize),
+    /// Like `JoinContext` but cut unevenly: left part gets `numerator / denominator`.
+    /// Parameters are block size, numerator and denominator.
+    JoinContextRatio(usize, usize, usize),
     /// Recursively cut in two with depjoin until given block size.
     DepJoin(usize),
     /// Advance locally with increasing block sizes. When stolen create tasks
@@ -33,6 +39,8 @@ impl Policy {
             Policy::Sequential => Policy::Sequential,
             Policy::Join(_) => Policy::Join(new_size),
             Policy::JoinContext(_) => Policy::JoinContext(new_size),
+            Policy::JoinRatio(_, n, d) => Policy::JoinRatio(new_size, *n, *d),
+            Policy::JoinContextRatio(_, n, d) => Policy::JoinContextRatio(new_size, *n, *d),
             Policy::DepJoin(_) => Policy::DepJoin(new_size),
             Policy::Adaptive(_, max) => Policy::Adaptive(new_size, *max),
             Policy::Rayon => Policy::Rayon,
@@ -45,6 +53,8 @@ impl Policy {
             Policy::Sequential => None,
             Policy::Join(s) => Some(*s),
             Policy::JoinContext(s) => Some(*s),
+            Policy::JoinRatio(s, _, _) => Some(*s),
+            Policy::JoinContextRatio(s, _, _) => Some(*s),
             Policy::DepJoin(s) => Some(*s),
             Policy::Adaptive(s, _) => Some(*s),
             Policy::Rayon => None,
@@ -1,8 +1,10 @@
+pub use crate::iter::bridge::AdaptiveParallelIterator;
 pub use crate::iter::str::AdaptiveString;
 pub use crate::iter::{
-    AdaptiveBlockedIteratorRunner, AdaptiveIndexedIterator, AdaptiveIndexedIteratorRunner,
-    AdaptiveIterator, AdaptiveIteratorRunner, FromAdaptiveBlockedIterator,
-    FromAdaptiveIndexedIterator, IntoAdaptiveIterator,
+    AdaptiveBlockedIteratorRunner, AdaptiveExtend, AdaptiveIndexedIterator,
+    AdaptiveIndexedIteratorRunner, AdaptiveIterator, AdaptiveIteratorRunner,
+    FromAdaptiveBlockedIterator, FromAdaptiveIndexedIterator, FromAdaptiveIterator,
+    IntoAdaptiveIterator,
 };
 pub use crate::policy::{AdaptiveRunner, AllAdaptiveRunner, BlockAdaptiveRunner};
 pub use crate::traits::{Divisible, DivisibleAtIndex, DivisibleIntoBlocks};
@@ -36,7 +36,13 @@ fn default_max_block_size(n: usize) -> usize {
 /// this allows us to ensure we enforce important bounds on sizes.
 fn compute_size<F: Fn(usize) -> usize>(n: usize, sizing_function: F) -> usize {
     let p = current_num_threads();
-    std::cmp::max(min(n / (2 * p), sizing_function(n)), 1)
+    let bound = n / (2 * p);
+    if bound == 0 {
+        // we would return 1 anyway but sizing functions might overflow on tiny inputs
+        1
+    } else {
+        std::cmp::max(min(bound, sizing_function(n)), 1)
+    }
 }
 
 pub(crate) fn schedule<F, RF>(
@@ -58,15 +64,25 @@ where
                 Policy::DefaultPolicy => compute_size(input.base_length(), default_min_block_size),
                 Policy::Join(block_size)
                 | Policy::JoinContext(block_size)
+                | Policy::JoinRatio(block_size, _, _)
+                | Policy::JoinContextRatio(block_size, _, _)
                 | Policy::DepJoin(block_size)
                 | Policy::Adaptive(block_size, _) => block_size,
                 Policy::Rayon => 1,
             };
             match policy {
                 Policy::Sequential => schedule_sequential(input, folder),
-                Policy::Join(_) => schedule_join(input, folder, reduce_function, block_size),
+                Policy::Join(_) => {
+                    schedule_join(input, folder, reduce_function, block_size, (1, 2))
+                }
                 Policy::JoinContext(_) => {
-                    schedule_join_context(input, folder, reduce_function, block_size)
+                    schedule_join_context(input, folder, reduce_function, block_size, (1, 2))
+                }
+                Policy::JoinRatio(_, n, d) => {
+                    schedule_join(input, folder, reduce_function, block_size, (n, d))
+                }
+                Policy::JoinContextRatio(_, n, d) => {
+                    schedule_join_context(input, folder, reduce_function, block_size, (n, d))
                 }
                 Policy::DepJoin(_) => schedule_depjoin(input, folder, reduce_function, block_size),
                 Policy::Adaptive(min, max) => schedule_adaptive(
@@ -117,11 +133,28 @@ fn schedule_sequential<F: Folder>(input: F::Input, folder: &F) -> F::Output {
     folder.to_output(io, i)
 }
 
+/// Divide input using given ratio (dividing evenly if ratio is one half).
+fn divide_with<I: Divisible>(input: I, (numerator, denominator): (usize, usize)) -> (I, I) {
+    if 2 * numerator == denominator {
+        input.divide()
+    } else {
+        input.divide_ratio(numerator, denominator)
+    }
+}
+
+/// Some inputs cannot always be cut (a string without line feeds when iterating on lines
+/// for example). They then divide into themselves and an empty input and we need to stop
+/// recursing. Inputs which always divide (all indexed ones) never get here.
+fn is_undivided<I: Divisible>(left: &I, right: &I) -> bool {
+    left.base_length() == 0 || right.base_length() == 0
+}
+
 fn schedule_join<F, RF>(
     input: F::Input,
     folder: &F,
     reduce_function: &RF,
     block_size: usize,
+    ratio: (usize, usize),
 ) -> F::Output
 where
     F: Folder,
@@ -131,10 +164,16 @@ where
     if len <= block_size {
         schedule_sequential(input, folder)
     } else {
-        let (i1, i2) = input.divide();
+        let (i1, i2) = divide_with(input, ratio);
+        if is_undivided(&i1, &i2) {
+            return reduce_function(
+                schedule_sequential(i1, folder),
+                schedule_sequential(i2, folder),
+            );
+        }
         let (r1, r2) = rayon::join(
-            || schedule_join(i1, folder, reduce_function, block_size),
-            || schedule_join(i2, folder, reduce_function, block_size),
+            || schedule_join(i1, folder, reduce_function, block_size, ratio),
+            || schedule_join(i2, folder, reduce_function, block_size, ratio),
         );
         reduce_function(r1, r2)
     }
@@ -145,6 +184,7 @@ fn schedule_join_context<F, RF>(
     folder: &F,
     reduce_function: &RF,
     block_size: usize,
+    ratio: (usize, usize),
 ) -> F::Output
 where
     F: Folder,
@@ -154,12 +194,18 @@ where
     if len <= block_size {
         schedule_sequential(input, folder)
     } else {
-        let (i1, i2) = input.divide();
+        let (i1, i2) = divide_with(input, ratio);
+        if is_undivided(&i1, &i2) {
+            return reduce_function(
+                schedule_sequential(i1, folder),
+                schedule_sequential(i2, folder),
+            );
+        }
         let (r1, r2) = rayon::join_context(
-            |_| schedule_join_context(i1, folder, reduce_function, block_size),
+            |_| schedule_join_context(i1, folder, reduce_function, block_size, ratio),
             |c| {
                 if c.migrated() {
-                    schedule_join_context(i2, folder, reduce_function, block_size)
+                    schedule_join_context(i2, folder, reduce_function, block_size, ratio)
                 } else {
                     schedule_sequential(i2, folder)
                 }
@@ -218,6 +264,12 @@ where
         schedule_sequential(input, folder)
     } else {
         let (i1, i2) = input.divide();
+        if is_undivided(&i1, &i2) {
+            return reduce_function(
+                schedule_sequential(i1, folder),
+                schedule_sequential(i2, folder),
+            );
+        }
         let (r1, r2) = rayon::join_context(
             |_| schedule_join_context_max_size(i1, folder, reduce_function, min_size, max_size),
             |c| {
@@ -250,6 +302,12 @@ where
         schedule_sequential(input, folder)
     } else {
         let (i1, i2) = input.divide();
+        if is_undivided(&i1, &i2) {
+            return reduce_function(
+                schedule_sequential(i1, folder),
+                schedule_sequential(i2, folder),
+            );
+        }
         depjoin(
             || schedule_depjoin(i1, folder, reduce_function, block_size),
             || schedule_depjoin(i2, folder, reduce_function, block_size),
@@ -661,3 +719,97 @@ fn slave_work<'scope, F>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ratio_index;
+
+    #[test]
+    fn tiny_inputs_get_unit_blocks() {
+        for n in 0..(2 * current_num_threads()) {
+            assert_eq!(compute_size(n, default_min_block_size), 1);
+            assert_eq!(compute_size(n, default_max_block_size), 1);
+        }
+        let n = 1_000_000;
+        let size = compute_size(n, default_min_block_size);
+        assert!((1..=n / (2 * current_num_threads())).contains(&size));
+    }
+
+    #[test]
+    fn undividable_inputs_stop_recursions() {
+        let line = "no line feed ".repeat(1000);
+        for &policy in &[
+            Policy::Join(1),
+            Policy::JoinContext(1),
+            Policy::JoinRatio(1, 1, 3),
+            Policy::JoinContextRatio(1, 1, 3),
+            Policy::DepJoin(1),
+            Policy::DefaultPolicy,
+        ] {
+            // dividing gives back the whole line and an empty input
+            assert_eq!(line.adapt_lines().with_policy(policy).count(), 1);
+            assert_eq!("🦀".adapt_chars().with_policy(policy).count(), 1);
+            // indexed inputs always divide: nothing changes for them
+            assert_eq!(
+                (0..10_000)
+                    .into_adapt_iter()
+                    .with_policy(policy)
+                    .sum::<usize>(),
+                49_995_000
+            );
+        }
+    }
+
+    /// Two ranges divided together: they stay aligned only if
+    /// all divisions reach both fields.
+    #[derive(crate::Divisible, crate::DivisibleIntoBlocks)]
+    struct Aligned {
+        left: std::ops::Range<usize>,
+        right: std::ops::Range<usize>,
+        #[divide_by(clone)]
+        offset: usize,
+    }
+
+    fn leaves(policy: Policy) -> Vec<(std::ops::Range<usize>, std::ops::Range<usize>)> {
+        Aligned {
+            left: 0..100,
+            right: 1000..1100,
+            offset: 1000,
+        }
+        .with_policy(policy)
+        .map_reduce(
+            |a| {
+                assert_eq!(a.offset, 1000);
+                vec![(a.left, a.right)]
+            },
+            |mut l, r| {
+                l.extend(r);
+                l
+            },
+        )
+    }
+
+    #[test]
+    fn ratios_reach_derived_fields() {
+        for &(numerator, denominator) in &[(1, 4), (3, 4), (1, 3), (1, 2)] {
+            // left parts are always divided: the first leaf is known
+            let first = ratio_index(100, numerator, denominator);
+            for &policy in &[
+                Policy::JoinRatio(first, numerator, denominator),
+                Policy::JoinContextRatio(first, numerator, denominator),
+            ] {
+                let leaves = leaves(policy);
+                assert_eq!(leaves[0].0, 0..first);
+                for (left, right) in &leaves {
+                    assert_eq!((left.start + 1000)..(left.end + 1000), *right);
+                }
+                assert!(leaves.iter().flat_map(|(left, _)| left.clone()).eq(0..100));
+            }
+        }
+        // join divides down to the block size
+        let leaves = leaves(Policy::JoinRatio(40, 1, 4));
+        let sizes: Vec<usize> = leaves.iter().map(|(left, _)| left.len()).collect();
+        assert_eq!(sizes, [25, 18, 14, 10, 33]);
+    }
+}
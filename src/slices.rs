@@ -76,6 +76,9 @@ impl<'a, T: 'a + Sync> Divisible for EdibleSlice<'a, T> {
             },
         )
     }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
 }
 
 impl<'a, T: 'a + Sync> DivisibleIntoBlocks for EdibleSlice<'a, T> {
@@ -158,6 +161,14 @@ impl<'a, T: 'a> EdibleSliceMut<'a, T> {
     pub fn into_remaining_slice(self) -> &'a mut [T] {
         &mut self.slice[self.used..]
     }
+    /// Return the part of the inner slice we already used.
+    pub fn used_slice(&mut self) -> &mut [T] {
+        &mut self.slice[..self.used]
+    }
+    /// Forget about what we used: the whole inner slice is remaining again.
+    pub fn rewind(&mut self) {
+        self.used = 0;
+    }
     /// Return an iterator on remaining elements (mutable).
     /// When the iterator drops we remember what's left unused.
     pub fn iter_mut(&mut self) -> EatingIteratorMut<T> {
@@ -240,6 +251,9 @@ impl<'a, T: 'a + Sync + Send> Divisible for EdibleSliceMut<'a, T> {
             },
         )
     }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
 }
 
 impl<'a, T: 'a + Send + Sync> DivisibleIntoBlocks for EdibleSliceMut<'a, T> {
@@ -6,19 +6,119 @@ use std::ptr;
 
 use crate::chunks::Chunks;
 use crate::policy::ParametrizedInput;
+use crate::utils::ratio_index;
 use crate::Policy;
 
 // markers for specialization
+/// `Power` of inputs which can only be divided in two (no control on where we cut).
+/// Only `Divisible` is implemented.
 pub struct BasicPower();
+/// `Power` of inputs which can be cut at a given index but where the index
+/// does not correspond to a fixed position in the final output (filters for example).
+/// `DivisibleIntoBlocks` is implemented.
 pub struct BlockedPower();
+/// `Power` of inputs where cutting at index *i* yields exactly *i* elements on the left.
+/// `DivisibleAtIndex` is implemented.
 pub struct IndexedPower();
+/// Not a `Power` but a marker for runners and activated inputs.
+/// It regroups `BlockedPower` and `IndexedPower` since both of them are processed
+/// with the same blocks based algorithms.
 pub struct BlockedOrMore();
 
+mod private {
+    pub trait Sealed {}
+    impl Sealed for super::BasicPower {}
+    impl Sealed for super::BlockedPower {}
+    impl Sealed for super::IndexedPower {}
+}
+
+/// Capabilities hierarchy for `Divisible::Power`.
+/// This trait is sealed: only `BasicPower`, `BlockedPower` and `IndexedPower` implement it.
+///
+/// The hierarchy is `BasicPower` < `BlockedPower` < `IndexedPower` and is checked
+/// at compile time :
+/// * `DivisibleIntoBlocks` requires a power of at least `BlockedPower`
+/// * `DivisibleAtIndex` requires `IndexedPower`
+/// * `IndexedPower` together with `DivisibleIntoBlocks` implies `DivisibleAtIndex`
+///
+/// # Example
+///
+/// ```compile_fail
+/// use rayon_adaptive::prelude::*;
+/// use rayon_adaptive::BasicPower;
+/// struct Halves(Vec<u32>);
+/// impl Divisible for Halves {
+///     type Power = BasicPower;
+///     fn base_length(&self) -> usize {
+///         self.0.len()
+///     }
+///     fn divide(mut self) -> (Self, Self) {
+///         let right = self.0.split_off(self.0.len() / 2);
+///         (self, Halves(right))
+///     }
+/// }
+/// // does not compile: we claimed `BasicPower`
+/// impl DivisibleIntoBlocks for Halves {
+///     fn divide_at(mut self, index: usize) -> (Self, Self) {
+///         let right = self.0.split_off(index);
+///         (self, Halves(right))
+///     }
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a division power",
+    note = "use one of `BasicPower`, `BlockedPower` or `IndexedPower`"
+)]
+pub trait PowerMarker: private::Sealed {}
+impl PowerMarker for BasicPower {}
+impl PowerMarker for BlockedPower {}
+impl PowerMarker for IndexedPower {}
+
+/// Powers allowing to cut at a given index (`BlockedPower` and `IndexedPower`).
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not powerful enough to divide into blocks",
+    note = "`DivisibleIntoBlocks` requires `Power = BlockedPower` or `Power = IndexedPower`"
+)]
+pub trait AtLeastBlocked: PowerMarker {}
+impl AtLeastBlocked for BlockedPower {}
+impl AtLeastBlocked for IndexedPower {}
+
+/// Inputs can be cut in two, in any way they like.
+///
+/// The `Power` associated type tells the scheduler what else we can do :
+/// * `BasicPower` if nothing more is available
+/// * `BlockedPower` if `DivisibleIntoBlocks` is implemented
+/// * `IndexedPower` if `DivisibleAtIndex` is implemented
 pub trait Divisible: Sized + Send + Sync {
-    type Power;
+    /// What kind of divisions are available.
+    type Power: PowerMarker;
     /// Divide ourselves.
     /// Dividing shall never fail.
     fn divide(self) -> (Self, Self);
+    /// Divide ourselves unevenly, the left part getting about
+    /// `numerator / denominator` of the work.
+    /// By default we just `divide`, which is all inputs with a `BasicPower` can do.
+    /// Inputs which are `DivisibleIntoBlocks` need to override it with
+    /// `DivisibleIntoBlocks::divide_at_ratio`, or `JoinRatio` and `JoinContextRatio`
+    /// policies will divide them evenly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// use rayon_adaptive::Policy;
+    /// assert_eq!((0..90).divide_ratio(1, 3), (0..30, 30..90));
+    /// // schedulers can also cut unevenly
+    /// let s: usize = (0..1000)
+    ///     .into_adapt_iter()
+    ///     .with_policy(Policy::JoinRatio(10, 1, 3))
+    ///     .sum();
+    /// assert_eq!(s, 999 * 500);
+    /// ```
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        let _ = (numerator, denominator);
+        self.divide()
+    }
     /// Return our length.
     /// This length is used by the scheduler.
     /// Its meaning is :
@@ -36,9 +136,63 @@ pub trait Divisible: Sized + Send + Sync {
     }
 }
 
-pub trait DivisibleIntoBlocks: Divisible {
+/// Inputs can be cut at any given index.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be divided into blocks",
+    note = "this operation requires `DivisibleIntoBlocks` (a `Power` of `BlockedPower` or more)"
+)]
+pub trait DivisibleIntoBlocks: Divisible<Power: AtLeastBlocked> {
     /// Divide ourselves where requested.
     fn divide_at(self, index: usize) -> (Self, Self);
+    /// Divide ourselves with `divide_at`, leaving about `numerator / denominator`
+    /// of our `base_length` on the left (see `utils::ratio_index`).
+    /// Route `Divisible::divide_ratio` here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// use rayon_adaptive::BlockedPower;
+    /// struct Values(Vec<u32>);
+    /// impl Divisible for Values {
+    ///     type Power = BlockedPower;
+    ///     fn base_length(&self) -> usize {
+    ///         self.0.len()
+    ///     }
+    ///     fn divide(self) -> (Self, Self) {
+    ///         let mid = self.0.len() / 2;
+    ///         self.divide_at(mid)
+    ///     }
+    ///     fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+    ///         self.divide_at_ratio(numerator, denominator)
+    ///     }
+    /// }
+    /// impl DivisibleIntoBlocks for Values {
+    ///     fn divide_at(mut self, index: usize) -> (Self, Self) {
+    ///         let right = self.0.split_off(index);
+    ///         (self, Values(right))
+    ///     }
+    /// }
+    /// let (left, right) = Values((0..100).collect()).divide_ratio(1, 4);
+    /// assert_eq!((left.0.len(), right.0.len()), (25, 75));
+    /// ```
+    fn divide_at_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        let index = ratio_index(self.base_length(), numerator, denominator);
+        self.divide_at(index)
+    }
+    /// Divide ourselves leaving `index` units of work (`base_length`) on the right.
+    /// This enables processing blocks starting from the end (see `find_last`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon_adaptive::prelude::*;
+    /// assert_eq!((0..10).divide_at_from_end(3), (0..7, 7..10));
+    /// ```
+    fn divide_at_from_end(self, index: usize) -> (Self, Self) {
+        let length = self.base_length();
+        self.divide_at(length - index)
+    }
     /// Divide ourselves keeping right part in self.
     /// Returns the left part.
     /// NB: this is useful for iterators creation.
@@ -62,7 +216,15 @@ pub trait DivisibleIntoBlocks: Divisible {
     }
 }
 
-pub trait DivisibleAtIndex: DivisibleIntoBlocks {}
+/// Inputs where cutting at index *i* puts exactly *i* elements on the left.
+/// It is automatically implemented for all `DivisibleIntoBlocks` with `IndexedPower`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be divided at exact indices",
+    note = "this operation requires `DivisibleAtIndex` (a `Power` of `IndexedPower`)"
+)]
+pub trait DivisibleAtIndex: DivisibleIntoBlocks<Power = IndexedPower> {}
+
+impl<I: DivisibleIntoBlocks<Power = IndexedPower>> DivisibleAtIndex for I {}
 
 impl<'a, T: Sync> Divisible for &'a [T] {
     type Power = IndexedPower;
@@ -73,6 +235,9 @@ impl<'a, T: Sync> Divisible for &'a [T] {
         let mid = self.len() / 2;
         self.split_at(mid)
     }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
 }
 
 impl<'a, T: Sync> DivisibleIntoBlocks for &'a [T] {
@@ -81,8 +246,6 @@ impl<'a, T: Sync> DivisibleIntoBlocks for &'a [T] {
     }
 }
 
-impl<'a, T: Sync> DivisibleAtIndex for &'a [T] {}
-
 //TODO: I don't get why the compiler requires send here
 impl<'a, T: 'a + Sync + Send> Divisible for &'a mut [T] {
     type Power = IndexedPower;
@@ -93,6 +256,9 @@ impl<'a, T: 'a + Sync + Send> Divisible for &'a mut [T] {
         let mid = self.base_length() / 2;
         self.split_at_mut(mid)
     }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
 }
 
 impl<'a, T: 'a + Sync + Send> DivisibleIntoBlocks for &'a mut [T] {
@@ -101,8 +267,6 @@ impl<'a, T: 'a + Sync + Send> DivisibleIntoBlocks for &'a mut [T] {
     }
 }
 
-impl<'a, T: 'a + Sync + Send> DivisibleAtIndex for &'a mut [T] {}
-
 //TODO: be more generic but it seems complex
 impl Divisible for Range<usize> {
     type Power = IndexedPower;
@@ -113,6 +277,9 @@ impl Divisible for Range<usize> {
         let mid = self.start + ExactSizeIterator::len(&self) / 2;
         (self.start..mid, mid..self.end)
     }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        self.divide_at_ratio(numerator, denominator)
+    }
 }
 
 //TODO: be more generic but it seems complex
@@ -124,5 +291,3 @@ impl DivisibleIntoBlocks for Range<usize> {
         )
     }
 }
-
-impl DivisibleAtIndex for Range<usize> {}
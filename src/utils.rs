@@ -13,6 +13,28 @@ pub fn fuse_slices<'a, 'b, 'c: 'a + 'b, T: 'c>(s1: &'a mut [T], s2: &'b mut [T])
     }
 }
 
+/// Return index at which to cut an input of given length
+/// in order to leave `numerator / denominator` of it on the left.
+/// When possible, we avoid empty parts.
+///
+/// # Example
+///
+/// ```
+/// use rayon_adaptive::utils::ratio_index;
+/// assert_eq!(ratio_index(90, 1, 3), 30);
+/// assert_eq!(ratio_index(2, 1, 10), 1);
+/// ```
+pub fn ratio_index(length: usize, numerator: usize, denominator: usize) -> usize {
+    assert!(numerator <= denominator && denominator > 0, "invalid ratio");
+    // avoid overflows on large lengths
+    let index = length / denominator * numerator + length % denominator * numerator / denominator;
+    if length < 2 {
+        index
+    } else {
+        std::cmp::max(1, std::cmp::min(index, length - 1))
+    }
+}
+
 /// iterate on starting_value * 2**i
 pub fn powers(starting_value: usize) -> impl Iterator<Item = usize> {
     (0..).scan(starting_value, |state, _| {
@@ -48,6 +70,19 @@ impl<'a, I: Divisible> Divisible for AbortingDivisible<'a, I> {
             },
         )
     }
+    fn divide_ratio(self, numerator: usize, denominator: usize) -> (Self, Self) {
+        let (left, right) = self.real_content.divide_ratio(numerator, denominator);
+        (
+            AbortingDivisible {
+                real_content: left,
+                abort: self.abort,
+            },
+            AbortingDivisible {
+                real_content: right,
+                abort: self.abort,
+            },
+        )
+    }
 }
 
 impl<'a, I: DivisibleIntoBlocks> DivisibleIntoBlocks for AbortingDivisible<'a, I> {
@@ -66,8 +101,6 @@ impl<'a, I: DivisibleIntoBlocks> DivisibleIntoBlocks for AbortingDivisible<'a, I
     }
 }
 
-impl<'a, I: DivisibleAtIndex> DivisibleAtIndex for AbortingDivisible<'a, I> {}
-
 impl<'a, I: IntoIterator> IntoIterator for AbortingDivisible<'a, I> {
     type IntoIter = I::IntoIter;
     type Item = I::Item;
@@ -75,3 +108,23 @@ impl<'a, I: IntoIterator> IntoIterator for AbortingDivisible<'a, I> {
         self.real_content.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extreme_ratios_still_divide() {
+        assert_eq!((ratio_index(0, 0, 3), ratio_index(0, 3, 3)), (0, 0));
+        assert_eq!((ratio_index(1, 0, 3), ratio_index(1, 3, 3)), (0, 1));
+        // larger inputs never give empty parts
+        for length in 2..100 {
+            assert_eq!(ratio_index(length, 0, 3), 1);
+            assert_eq!(ratio_index(length, 3, 3), length - 1);
+        }
+        // no overflows on huge lengths
+        assert_eq!(ratio_index(usize::MAX, 0, 7), 1);
+        assert_eq!(ratio_index(usize::MAX, 7, 7), usize::MAX - 1);
+        assert_eq!(ratio_index(usize::MAX, 1, 2), usize::MAX / 2);
+    }
+}
@@ -0,0 +1,8 @@
+//! Compile tests for the `Divisible`, `DivisibleIntoBlocks` and `DivisibleAtIndex` derives.
+//! Regenerate expected errors with `TRYBUILD=overwrite cargo test --test derive`.
+#[test]
+fn derives() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/derive/pass/*.rs");
+    t.compile_fail("tests/derive/fail/*.rs");
+}
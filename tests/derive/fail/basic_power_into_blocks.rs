@@ -0,0 +1,7 @@
+use rayon_adaptive::{BasicPower, Divisible, DivisibleIntoBlocks};
+
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(BasicPower)]
+struct Values<'a>(&'a [u32]);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use rayon_adaptive::{BlockedPower, Divisible, DivisibleAtIndex, DivisibleIntoBlocks};
+
+#[derive(Divisible, DivisibleIntoBlocks, DivisibleAtIndex)]
+#[power(BlockedPower)]
+struct Values<'a>(&'a [u32]);
+
+fn main() {}
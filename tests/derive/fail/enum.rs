@@ -0,0 +1,9 @@
+use rayon_adaptive::Divisible;
+
+#[derive(Divisible)]
+enum Either<'a> {
+    Left(&'a [u32]),
+    Right(&'a [u32]),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rayon_adaptive::{Divisible, DivisibleAtIndex, DivisibleIntoBlocks};
+
+// lines are not indexed so neither are we
+#[derive(Divisible, DivisibleIntoBlocks, DivisibleAtIndex)]
+struct Lines<'a> {
+    lines: rayon_adaptive::iter::str::AdaptiveLines<'a>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use rayon_adaptive::Divisible;
+
+#[derive(Divisible)]
+#[power(u32)]
+struct Values<'a>(&'a [u32]);
+
+fn main() {}
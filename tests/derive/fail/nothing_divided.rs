@@ -0,0 +1,11 @@
+use rayon_adaptive::Divisible;
+
+#[derive(Divisible)]
+struct Constants {
+    #[divide_by(clone)]
+    factor: u32,
+    #[divide_by(default)]
+    names: Vec<String>,
+}
+
+fn main() {}
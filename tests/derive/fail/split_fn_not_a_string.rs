@@ -0,0 +1,10 @@
+use rayon_adaptive::Divisible;
+
+#[derive(Divisible)]
+struct Budgeted<'a> {
+    values: &'a [u32],
+    #[divide_by(split_fn = 3)]
+    budget: usize,
+}
+
+fn main() {}
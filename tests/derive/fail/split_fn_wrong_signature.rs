@@ -0,0 +1,14 @@
+use rayon_adaptive::Divisible;
+
+fn halve(budget: usize) -> usize {
+    budget / 2
+}
+
+#[derive(Divisible)]
+struct Budgeted<'a> {
+    values: &'a [u32],
+    #[divide_by(split_fn = "halve")]
+    budget: usize,
+}
+
+fn main() {}
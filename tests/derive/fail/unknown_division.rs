@@ -0,0 +1,10 @@
+use rayon_adaptive::Divisible;
+
+#[derive(Divisible)]
+struct Scaled<'a> {
+    values: &'a [u32],
+    #[divide_by(copy)]
+    factor: u32,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use rayon_adaptive::prelude::*;
+use rayon_adaptive::{Divisible, DivisibleIntoBlocks, IndexedPower};
+
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(IndexedPower)]
+struct Scaled<'a> {
+    values: &'a [u32],
+    #[divide_by(clone)]
+    factor: u32,
+}
+
+fn main() {
+    let v: Vec<u32> = (0..100).collect();
+    let (left, right) = Scaled { values: &v, factor: 2 }.divide_at(30);
+    assert_eq!((left.values.len(), left.factor), (30, 2));
+    assert_eq!((right.values[0], right.factor), (30, 2));
+    let (left, right) = Scaled { values: &v, factor: 3 }.divide();
+    assert_eq!((left.base_length(), right.base_length()), (50, 50));
+    assert_eq!((left.factor, right.factor), (3, 3));
+}
@@ -0,0 +1,14 @@
+use rayon_adaptive::prelude::*;
+use rayon_adaptive::{Divisible, DivisibleIntoBlocks};
+
+// tuple structures are supported too
+#[derive(Divisible, DivisibleIntoBlocks)]
+struct Logged<'a>(&'a [u32], #[divide_by(default)] Vec<String>);
+
+fn main() {
+    let v: Vec<u32> = (0..10).collect();
+    let (left, right) = Logged(&v, vec!["started".to_string()]).divide_at(4);
+    assert_eq!((left.0, right.0), (&v[..4], &v[4..]));
+    assert_eq!(left.1, vec!["started".to_string()]);
+    assert!(right.1.is_empty());
+}
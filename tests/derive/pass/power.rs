@@ -0,0 +1,48 @@
+use rayon_adaptive::prelude::*;
+use rayon_adaptive::{
+    BasicPower, BlockedPower, Divisible, DivisibleAtIndex, DivisibleIntoBlocks, IndexedPower,
+};
+
+fn power<D: Divisible<Power = P>, P>(_: &D) -> std::marker::PhantomData<P> {
+    std::marker::PhantomData
+}
+
+// without attribute we take the power of the first divided field
+#[derive(Divisible, DivisibleIntoBlocks, DivisibleAtIndex)]
+struct Inferred<'a> {
+    #[divide_by(clone)]
+    name: String,
+    values: &'a [u32],
+    range: std::ops::Range<usize>,
+}
+
+// powers can be lowered
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(BlockedPower)]
+struct Blocked<'a>(&'a [u32]);
+
+#[derive(Divisible)]
+#[power(BasicPower)]
+struct Basic<'a>(&'a [u32]);
+
+// generic powers
+#[derive(Divisible, DivisibleIntoBlocks)]
+#[power(D::Power)]
+struct Wrapped<D: DivisibleIntoBlocks>(D);
+
+fn main() {
+    let v: Vec<u32> = (0..10).collect();
+    let inferred = Inferred {
+        name: "v".to_string(),
+        values: &v,
+        range: 0..8,
+    };
+    let _: std::marker::PhantomData<IndexedPower> = power(&inferred);
+    // the shortest divided field gives the length
+    assert_eq!(inferred.base_length(), 8);
+    let _: std::marker::PhantomData<BlockedPower> = power(&Blocked(&v));
+    let _: std::marker::PhantomData<BasicPower> = power(&Basic(&v));
+    let _: std::marker::PhantomData<IndexedPower> = power(&Wrapped(0..3));
+    let (left, right) = Wrapped(0..10).divide_at(4);
+    assert_eq!((left.0, right.0), (0..4, 4..10));
+}
@@ -0,0 +1,25 @@
+use rayon_adaptive::prelude::*;
+use rayon_adaptive::{Divisible, DivisibleIntoBlocks};
+
+mod splits {
+    /// Both halves share the remaining budget.
+    pub fn halve(budget: usize) -> (usize, usize) {
+        (budget / 2, budget - budget / 2)
+    }
+}
+
+#[derive(Divisible, DivisibleIntoBlocks)]
+struct Budgeted {
+    range: std::ops::Range<usize>,
+    #[divide_by(split_fn = "splits::halve")]
+    budget: usize,
+}
+
+fn main() {
+    let (left, right) = Budgeted { range: 0..10, budget: 7 }.divide_at(3);
+    assert_eq!((left.range, left.budget), (0..3, 3));
+    assert_eq!((right.range, right.budget), (3..10, 4));
+    let (left, right) = Budgeted { range: 0..10, budget: 8 }.divide();
+    assert_eq!((left.range, right.range), (0..5, 5..10));
+    assert_eq!((left.budget, right.budget), (4, 4));
+}